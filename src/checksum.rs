@@ -0,0 +1,308 @@
+//! Checksums and reader and writer adapters that compute them on the fly.
+
+use crate::{Endianess, Error, Primitive, ReadVal, Result, WriteVal};
+use std::any::type_name;
+use std::io::{self, Read, Write};
+
+mod adler;
+mod crc;
+mod fletcher;
+
+pub use adler::Adler32;
+pub use crc::{Crc16, Crc32, Crc64};
+pub use fletcher::{Fletcher16, Fletcher32};
+
+/// Running checksum over a sequence of bytes.
+pub trait Checksum {
+    type Output: Copy;
+
+    /// Adds bytes to the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of all bytes added since the last reset.
+    fn value(&self) -> Self::Output;
+
+    /// Starts over as if no bytes were added.
+    fn reset(&mut self);
+}
+
+/// Checksum algorithms for the range methods like `ReadValAt::checksum_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+    Crc16Arc,
+    Crc16CcittFalse,
+    Crc16Xmodem,
+    Crc16Modbus,
+    Crc16Kermit,
+    Crc32,
+    Crc32c,
+    Crc64Ecma,
+    Crc64Xz,
+    Adler32,
+    Fletcher16,
+    Fletcher32,
+}
+
+impl Algorithm {
+    /// Returns the size of the checksum in bytes.
+    pub const fn size(self) -> usize {
+        use Algorithm::*;
+        match self {
+            Crc16Arc | Crc16CcittFalse | Crc16Xmodem | Crc16Modbus | Crc16Kermit | Fletcher16 => 2,
+            Crc32 | Crc32c | Adler32 | Fletcher32 => 4,
+            Crc64Ecma | Crc64Xz => 8,
+        }
+    }
+
+    /// Computes the checksum of the next `len` bytes of the reader.
+    pub(crate) fn compute<R: Read + ?Sized>(self, reader: &mut R, len: u64) -> Result<u64> {
+        match self {
+            Algorithm::Crc16Arc => sum(Crc16::arc(), reader, len).map(u64::from),
+            Algorithm::Crc16CcittFalse => sum(Crc16::ccitt_false(), reader, len).map(u64::from),
+            Algorithm::Crc16Xmodem => sum(Crc16::xmodem(), reader, len).map(u64::from),
+            Algorithm::Crc16Modbus => sum(Crc16::modbus(), reader, len).map(u64::from),
+            Algorithm::Crc16Kermit => sum(Crc16::kermit(), reader, len).map(u64::from),
+            Algorithm::Crc32 => sum(Crc32::ieee(), reader, len).map(u64::from),
+            Algorithm::Crc32c => sum(Crc32::castagnoli(), reader, len).map(u64::from),
+            Algorithm::Crc64Ecma => sum(Crc64::ecma(), reader, len),
+            Algorithm::Crc64Xz => sum(Crc64::xz(), reader, len),
+            Algorithm::Adler32 => sum(Adler32::new(), reader, len).map(u64::from),
+            Algorithm::Fletcher16 => sum(Fletcher16::new(), reader, len).map(u64::from),
+            Algorithm::Fletcher32 => sum(Fletcher32::new(), reader, len).map(u64::from),
+        }
+    }
+}
+
+/// Adds the next `len` bytes of the reader to the checksum and returns the value.
+pub(crate) fn sum<C: Checksum, R: Read + ?Sized>(
+    mut checksum: C,
+    reader: &mut R,
+    len: u64,
+) -> Result<C::Output> {
+    let mut buf = [0; 8192];
+    let mut left = len;
+    while left > 0 {
+        let chunk = &mut buf[..left.min(8192) as usize];
+        reader
+            .read_exact(chunk)
+            .map_err(|e| Error::for_value(e, None, "checksum range", len as usize))?;
+        checksum.update(chunk);
+        left -= chunk.len() as u64;
+    }
+    Ok(checksum.value())
+}
+
+/// Wrapper for `Read` that adds all bytes read to a checksum.
+///
+/// Stopping the checksum lets bytes pass through without being added,
+/// e.g. for headers that are not covered or for the stored checksum itself.
+pub struct ChecksumReader<R: Read, C: Checksum> {
+    inner: R,
+    checksum: C,
+    running: bool,
+    pos: u64,
+}
+
+impl<R: Read, C: Checksum> ChecksumReader<R, C> {
+    pub fn new(inner: R, checksum: C) -> Self {
+        Self {
+            inner,
+            checksum,
+            running: true,
+            pos: 0,
+        }
+    }
+
+    /// Returns the checksum of the bytes read while it was running.
+    pub fn value(&self) -> C::Output {
+        self.checksum.value()
+    }
+
+    /// Resumes adding bytes to the checksum.
+    pub fn start(&mut self) {
+        self.running = true;
+    }
+
+    /// Pauses the checksum, bytes read until `start` is called are not added.
+    pub fn stop(&mut self) {
+        self.running = false;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Resets the checksum, e.g. at the start of a new block.
+    pub fn reset(&mut self) {
+        self.checksum.reset();
+    }
+
+    /// Returns the number of bytes read so far, including those not added to the checksum.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// **Note:** bytes read from the inner reader directly are not added to the checksum.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the stored checksum and compares it with the running one.
+    ///
+    /// The stored bytes are not added to the checksum.
+    pub fn verify<const S: usize>(&mut self, endianess: Endianess) -> Result<()>
+    where
+        C::Output: Primitive<C::Output, S> + PartialEq + Into<u64>,
+    {
+        let actual = self.value();
+        let offset = self.pos;
+        let running = self.running;
+        self.running = false;
+        let stored = match endianess {
+            Endianess::Little => self.read_le::<C::Output, C::Output, S>(),
+            Endianess::Big => self.read_be::<C::Output, C::Output, S>(),
+            Endianess::Native => self.read_ne::<C::Output, C::Output, S>(),
+        };
+        self.running = running;
+
+        let stored = stored.map_err(|e| e.with_offset(Some(offset)))?;
+        match stored == actual {
+            true => Ok(()),
+            false => Err(Error::ChecksumMismatch {
+                offset: Some(offset),
+                type_name: type_name::<C>().rsplit("::").next().unwrap(),
+                expected: stored.into(),
+                actual: actual.into(),
+            }),
+        }
+    }
+}
+
+impl<R: Read> ChecksumReader<R, Crc32> {
+    /// Reads a little endian CRC-32 and compares it with the running one, like in gzip and ZIP.
+    pub fn verify_crc32_le(&mut self) -> Result<()> {
+        self.verify(Endianess::Little)
+    }
+
+    /// Reads a big endian CRC-32 and compares it with the running one, like in PNG.
+    pub fn verify_crc32_be(&mut self) -> Result<()> {
+        self.verify(Endianess::Big)
+    }
+}
+
+impl<R: Read, C: Checksum> Read for ChecksumReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if self.running {
+            self.checksum.update(&buf[..read]);
+        }
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+/// Wrapper for `Write` that adds all bytes written to a checksum.
+pub struct ChecksumWriter<W: Write, C: Checksum> {
+    inner: W,
+    checksum: C,
+    running: bool,
+}
+
+impl<W: Write, C: Checksum> ChecksumWriter<W, C> {
+    pub fn new(inner: W, checksum: C) -> Self {
+        Self {
+            inner,
+            checksum,
+            running: true,
+        }
+    }
+
+    /// Returns the checksum of the bytes written while it was running.
+    pub fn value(&self) -> C::Output {
+        self.checksum.value()
+    }
+
+    /// Resumes adding bytes to the checksum.
+    pub fn start(&mut self) {
+        self.running = true;
+    }
+
+    /// Pauses the checksum, bytes written until `start` is called are not added.
+    pub fn stop(&mut self) {
+        self.running = false;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Resets the checksum, e.g. at the start of a new block.
+    pub fn reset(&mut self) {
+        self.checksum.reset();
+    }
+
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// **Note:** bytes written to the inner writer directly are not added to the checksum.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the running checksum, the written bytes are not added to it.
+    pub fn write_checksum<const S: usize>(&mut self, endianess: Endianess) -> Result<()>
+    where
+        C::Output: Primitive<C::Output, S>,
+    {
+        let value = self.value();
+        let running = self.running;
+        self.running = false;
+        let result = match endianess {
+            Endianess::Little => self.write_le(value),
+            Endianess::Big => self.write_be(value),
+            Endianess::Native => self.write_ne(value),
+        };
+        self.running = running;
+        result
+    }
+}
+
+impl<W: Write, C: Checksum> Write for ChecksumWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if self.running {
+            self.checksum.update(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
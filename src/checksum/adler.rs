@@ -0,0 +1,48 @@
+use super::Checksum;
+
+const MOD: u32 = 65521;
+
+/// Largest number of bytes that can be summed before `b` could overflow.
+const NMAX: usize = 5552;
+
+/// Adler-32 checksum used by zlib.
+#[derive(Clone, Copy, Debug)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    pub const fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(NMAX) {
+            for byte in chunk {
+                self.a += *byte as u32;
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+
+    fn value(&self) -> u32 {
+        self.b << 16 | self.a
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
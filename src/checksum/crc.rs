@@ -0,0 +1,146 @@
+use super::Checksum;
+
+macro_rules! crc {
+    ($name:ident, $type:ty, $bits:literal) => {
+        /// Table-driven CRC with a width of
+        #[doc = concat!(stringify!($bits), " bits.")]
+        ///
+        /// Use one of the presets or `new` with the parameters from a CRC catalogue.
+        #[derive(Clone)]
+        pub struct $name {
+            table: [$type; 256],
+            init: $type,
+            xorout: $type,
+            reflected: bool,
+            crc: $type,
+        }
+
+        impl $name {
+            /// Creates a CRC with the (unreflected) polynomial `poly`.
+            ///
+            /// `reflected` processes the bits of each byte least significant bit first
+            /// and reflects the result, as most CRCs on little endian hardware do.
+            pub const fn new(poly: $type, init: $type, reflected: bool, xorout: $type) -> Self {
+                let mut table = [0; 256];
+                let mut i = 0;
+                while i < 256 {
+                    let mut crc = i as $type;
+                    let mut bit = 0;
+                    if reflected {
+                        let poly = poly.reverse_bits();
+                        while bit < 8 {
+                            crc = if crc & 1 != 0 {
+                                crc >> 1 ^ poly
+                            } else {
+                                crc >> 1
+                            };
+                            bit += 1;
+                        }
+                    } else {
+                        crc <<= $bits - 8;
+                        while bit < 8 {
+                            crc = if crc >> ($bits - 1) != 0 {
+                                crc << 1 ^ poly
+                            } else {
+                                crc << 1
+                            };
+                            bit += 1;
+                        }
+                    }
+                    table[i] = crc;
+                    i += 1;
+                }
+
+                // the register is kept reflected, so the initial value is as well
+                let init = if reflected { init.reverse_bits() } else { init };
+                Self {
+                    table,
+                    init,
+                    xorout,
+                    reflected,
+                    crc: init,
+                }
+            }
+        }
+
+        impl Checksum for $name {
+            type Output = $type;
+
+            fn update(&mut self, bytes: &[u8]) {
+                let mut crc = self.crc;
+                for byte in bytes {
+                    crc = match self.reflected {
+                        true => self.table[((crc ^ *byte as $type) & 0xff) as usize] ^ crc >> 8,
+                        false => {
+                            self.table[((crc >> ($bits - 8)) as u8 ^ byte) as usize] ^ crc << 8
+                        }
+                    };
+                }
+                self.crc = crc;
+            }
+
+            fn value(&self) -> $type {
+                self.crc ^ self.xorout
+            }
+
+            fn reset(&mut self) {
+                self.crc = self.init;
+            }
+        }
+    };
+}
+
+crc!(Crc16, u16, 16);
+crc!(Crc32, u32, 32);
+crc!(Crc64, u64, 64);
+
+impl Crc16 {
+    /// CRC-16/ARC, also known as CRC-16/IBM or plain CRC-16.
+    pub const fn arc() -> Self {
+        Self::new(0x8005, 0, true, 0)
+    }
+
+    /// CRC-16/CCITT-FALSE (CRC-16/IBM-3740).
+    pub const fn ccitt_false() -> Self {
+        Self::new(0x1021, 0xffff, false, 0)
+    }
+
+    /// CRC-16/XMODEM, used by XMODEM and ZMODEM.
+    pub const fn xmodem() -> Self {
+        Self::new(0x1021, 0, false, 0)
+    }
+
+    /// CRC-16/MODBUS, stored little endian after each Modbus RTU frame.
+    pub const fn modbus() -> Self {
+        Self::new(0x8005, 0xffff, true, 0)
+    }
+
+    /// CRC-16/KERMIT, the reflected CCITT CRC.
+    pub const fn kermit() -> Self {
+        Self::new(0x1021, 0, true, 0)
+    }
+}
+
+impl Crc32 {
+    /// CRC-32/ISO-HDLC used by PNG, ZIP, gzip and Ethernet.
+    pub const fn ieee() -> Self {
+        Self::new(0x04c1_1db7, 0xffff_ffff, true, 0xffff_ffff)
+    }
+
+    /// CRC-32C used by iSCSI, ext4 and Btrfs.
+    pub const fn castagnoli() -> Self {
+        Self::new(0x1edc_6f41, 0xffff_ffff, true, 0xffff_ffff)
+    }
+}
+
+impl Crc64 {
+    /// CRC-64/ECMA-182.
+    pub const fn ecma() -> Self {
+        Self::new(0x42f0_e1eb_a9ea_3693, 0, false, 0)
+    }
+
+    /// CRC-64/XZ used by the xz format.
+    pub const fn xz() -> Self {
+        Self::new(0x42f0_e1eb_a9ea_3693, u64::MAX, true, u64::MAX)
+    }
+}
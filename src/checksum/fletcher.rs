@@ -0,0 +1,83 @@
+use super::Checksum;
+
+/// Fletcher-16 checksum over bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fletcher16 {
+    a: u16,
+    b: u16,
+}
+
+impl Fletcher16 {
+    pub const fn new() -> Self {
+        Self { a: 0, b: 0 }
+    }
+}
+
+impl Checksum for Fletcher16 {
+    type Output = u16;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.a = (self.a + *byte as u16) % 255;
+            self.b = (self.b + self.a) % 255;
+        }
+    }
+
+    fn value(&self) -> u16 {
+        self.b << 8 | self.a
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Fletcher-32 checksum over little endian 16-bit words.
+///
+/// An odd trailing byte is padded with a zero byte.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fletcher32 {
+    a: u32,
+    b: u32,
+    pending: Option<u8>,
+}
+
+impl Fletcher32 {
+    pub const fn new() -> Self {
+        Self {
+            a: 0,
+            b: 0,
+            pending: None,
+        }
+    }
+
+    fn add(&mut self, word: u16) {
+        self.a = (self.a + word as u32) % 65535;
+        self.b = (self.b + self.a) % 65535;
+    }
+}
+
+impl Checksum for Fletcher32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            match self.pending.take() {
+                Some(low) => self.add(u16::from_le_bytes([low, *byte])),
+                None => self.pending = Some(*byte),
+            }
+        }
+    }
+
+    fn value(&self) -> u32 {
+        let mut padded = *self;
+        if let Some(low) = padded.pending.take() {
+            padded.add(low as u16);
+        }
+        padded.b << 16 | padded.a
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
@@ -0,0 +1,176 @@
+//! Compression and decompression: DEFLATE with zlib and gzip framing, RLE, PackBits, LZ4 blocks and LZSS.
+//!
+//! The readers and writers are plain `Read` and `Write` adapters, so all methods of
+//! `ReadVal` and `WriteVal` work on the decompressed data.
+//! The readers of the lightweight codecs take an optional limit on the decompressed size,
+//! exceeding it fails with `Error::LimitExceeded` to guard against decompression bombs.
+
+use crate::{Error, Result, limit::read_bytes};
+use std::io::{self, Read, Write};
+
+mod deflate;
+mod gzip;
+mod inflate;
+mod lz4;
+mod lzss;
+mod matcher;
+mod packbits;
+mod rle;
+mod zlib;
+
+pub use deflate::DeflateWriter;
+pub use gzip::{GzipHeader, GzipReader, GzipWriter};
+pub use inflate::InflateReader;
+pub use lz4::{Lz4BlockReader, Lz4BlockWriter};
+pub use lzss::{LzssFormat, LzssReader, LzssWriter, Reference};
+pub use packbits::{PackBitsReader, PackBitsWriter};
+pub use rle::{RleReader, RleWriter};
+pub use zlib::{ZlibReader, ZlibWriter};
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+fn invalid(type_name: &'static str, reason: &'static str) -> Error {
+    Error::InvalidEncoding {
+        offset: None,
+        type_name,
+        reason,
+    }
+}
+
+/// Reads a byte, returns `None` at the end of the stream.
+fn read_byte_opt<R: Read + ?Sized>(reader: &mut R) -> Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        return match reader.read(&mut byte) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(byte[0])),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(e.into()),
+        };
+    }
+}
+
+/// Decompressed bytes waiting to be read, keeping the last `window` bytes for matches.
+struct Output {
+    buf: Vec<u8>,
+    pos: usize,
+    window: usize,
+    total: u64,
+    limit: u64,
+}
+
+impl Output {
+    fn new(window: usize, limit: u64) -> Self {
+        Self {
+            buf: Vec::new(),
+            pos: 0,
+            window,
+            total: 0,
+            limit,
+        }
+    }
+
+    fn available(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Checks that `len` more bytes fit into the limit.
+    fn reserve(&mut self, len: usize) -> Result<()> {
+        let requested = self.total + len as u64;
+        if requested > self.limit {
+            return Err(Error::LimitExceeded {
+                offset: None,
+                requested,
+                limit: self.limit,
+            });
+        }
+        self.total = requested;
+        Ok(())
+    }
+
+    fn push(&mut self, byte: u8, count: usize) -> Result<()> {
+        self.reserve(count)?;
+        self.buf.resize(self.buf.len() + count, byte);
+        Ok(())
+    }
+
+    /// Reads `len` literal bytes from the reader.
+    fn read_from<R: Read + ?Sized>(
+        &mut self,
+        reader: &mut R,
+        len: usize,
+        type_name: &'static str,
+    ) -> Result<()> {
+        self.reserve(len)?;
+        let bytes =
+            read_bytes(reader, len).map_err(|e| Error::for_value(e, None, type_name, len))?;
+        self.buf.extend(bytes);
+        Ok(())
+    }
+
+    /// Copies `len` bytes starting `dist` bytes back, the ranges may overlap.
+    fn copy_match(&mut self, dist: usize, len: usize, type_name: &'static str) -> Result<()> {
+        if dist == 0 || dist > self.buf.len() {
+            return Err(invalid(type_name, "match distance out of range"));
+        }
+        self.reserve(len)?;
+        let start = self.buf.len() - dist;
+        for i in 0..len {
+            self.buf.push(self.buf[start + i]);
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> usize {
+        let len = buf.len().min(self.available());
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+
+        if self.pos == self.buf.len() && self.pos > self.window.max(4096) * 2 {
+            self.buf.drain(..self.pos - self.window);
+            self.pos = self.window;
+        }
+        len
+    }
+}
+
+/// Framing of a DEFLATE stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Raw DEFLATE without header and trailer.
+    Deflate,
+    /// zlib header and Adler-32 trailer (RFC 1950).
+    Zlib,
+    /// gzip header and CRC-32 trailer (RFC 1952).
+    Gzip,
+}
+
+/// Decompresses everything from the reader into the target and returns the decompressed size.
+///
+/// Fails once more than `limit` bytes are decompressed.
+pub(crate) fn decompress<R: Read>(
+    reader: R,
+    format: Format,
+    limit: u64,
+    target: &mut dyn Write,
+) -> Result<u64> {
+    Ok(match format {
+        Format::Deflate => io::copy(&mut InflateReader::with_limit(reader, limit), target)?,
+        Format::Zlib => io::copy(&mut ZlibReader::with_limit(reader, limit)?, target)?,
+        Format::Gzip => io::copy(&mut GzipReader::with_limit(reader, limit)?, target)?,
+    })
+}
@@ -0,0 +1,156 @@
+use super::{DIST_BASE, DIST_EXTRA, LENGTH_BASE, LENGTH_EXTRA, matcher::Matcher};
+use std::io::{self, Write};
+
+/// Size of the sliding window, matches can reach back this far.
+const WINDOW: usize = 32768;
+
+/// Amount of input compressed into one block.
+const BLOCK: usize = 65536;
+
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+/// Wrapper for `Write` that compresses everything written to it as a raw DEFLATE stream (RFC 1951).
+///
+/// Uses LZ77 with hash chains and the fixed Huffman codes, which is fast and simple but compresses less than zlib.
+/// Call `finish` to write the final block, dropping the writer leaves the stream incomplete.
+pub struct DeflateWriter<W: Write> {
+    inner: W,
+    out: Vec<u8>,
+    bits: u64,
+    count: u32,
+    data: Vec<u8>,
+    start: usize,
+}
+
+impl<W: Write> DeflateWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            out: Vec::new(),
+            bits: 0,
+            count: 0,
+            data: Vec::new(),
+            start: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// **Note:** writing to the inner writer directly corrupts the compressed stream.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Compresses the remaining data, writes the final block and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.compress_block(true)?;
+        if self.count > 0 {
+            self.out.push(self.bits as u8);
+        }
+        self.inner.write_all(&self.out)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn put_bits(&mut self, value: u32, n: u32) {
+        self.bits |= (value as u64) << self.count;
+        self.count += n;
+        while self.count >= 8 {
+            self.out.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Writes a Huffman code, which is stored most significant bit first.
+    fn put_code(&mut self, code: u32, n: u32) {
+        self.put_bits(code.reverse_bits() >> (32 - n), n);
+    }
+
+    fn put_literal(&mut self, symbol: u32) {
+        match symbol {
+            0..144 => self.put_code(0x30 + symbol, 8),
+            144..256 => self.put_code(0x190 + symbol - 144, 9),
+            256..280 => self.put_code(symbol - 256, 7),
+            _ => self.put_code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn put_match(&mut self, len: usize, dist: usize) {
+        let code = LENGTH_BASE
+            .iter()
+            .rposition(|base| *base as usize <= len)
+            .unwrap();
+        self.put_literal(257 + code as u32);
+        self.put_bits(
+            (len - LENGTH_BASE[code] as usize) as u32,
+            LENGTH_EXTRA[code],
+        );
+
+        let code = DIST_BASE
+            .iter()
+            .rposition(|base| *base as usize <= dist)
+            .unwrap();
+        self.put_code(code as u32, 5);
+        self.put_bits((dist - DIST_BASE[code] as usize) as u32, DIST_EXTRA[code]);
+    }
+
+    /// Compresses the pending data as one block with the fixed Huffman codes.
+    fn compress_block(&mut self, last: bool) -> io::Result<()> {
+        self.put_bits(last as u32, 1);
+        self.put_bits(1, 2);
+
+        let data = std::mem::take(&mut self.data);
+        let mut matcher = Matcher::new(&data, MIN_MATCH);
+        // the window kept from earlier blocks can be matched as well
+        for pos in 0..self.start {
+            matcher.insert(pos);
+        }
+        let mut pos = self.start;
+        while pos < data.len() {
+            let (len, dist) = matcher.find(pos, WINDOW, MAX_MATCH);
+            if len == 0 {
+                self.put_literal(data[pos] as u32);
+                matcher.insert(pos);
+                pos += 1;
+                continue;
+            }
+            self.put_match(len, dist);
+            for pos in pos..pos + len {
+                matcher.insert(pos);
+            }
+            pos += len;
+        }
+        self.put_literal(256);
+        self.data = data;
+        self.start = pos;
+
+        self.inner.write_all(&self.out)?;
+        self.out.clear();
+        // drop the data that is too far back to be matched
+        let drop = self.data.len().saturating_sub(WINDOW);
+        self.data.drain(..drop);
+        self.start -= drop;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for DeflateWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        if self.data.len() - self.start >= BLOCK {
+            self.compress_block(false)?;
+        }
+        Ok(buf.len())
+    }
+
+    /// Flushes the inner writer, the pending input stays buffered until a block is full or `finish` is called.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,265 @@
+use super::{DeflateWriter, InflateReader, invalid};
+use crate::{
+    ChecksumReader, Error, ReadVal, Result,
+    checksum::{Checksum, Crc32},
+};
+use std::io::{self, Read, Write};
+
+const TEXT: u8 = 1;
+const HEADER_CRC: u8 = 2;
+const EXTRA: u8 = 4;
+const NAME: u8 = 8;
+const COMMENT: u8 = 16;
+
+/// Metadata stored in a gzip header.
+///
+/// Names and comments are ISO 8859-1 and kept as raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GzipHeader {
+    /// Modification time as a Unix timestamp, 0 if unknown.
+    pub mtime: u32,
+    /// Operating system that created the file, 255 if unknown.
+    pub os: u8,
+    pub text: bool,
+    pub extra: Option<Vec<u8>>,
+    pub name: Option<Vec<u8>>,
+    pub comment: Option<Vec<u8>>,
+}
+
+impl Default for GzipHeader {
+    fn default() -> Self {
+        Self {
+            mtime: 0,
+            os: 255,
+            text: false,
+            extra: None,
+            name: None,
+            comment: None,
+        }
+    }
+}
+
+fn read_zero_terminated<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    loop {
+        match reader.read_u8()? {
+            0 => return Ok(bytes),
+            byte => bytes.push(byte),
+        }
+    }
+}
+
+impl GzipHeader {
+    fn read<R: Read>(inner: &mut R) -> Result<Self> {
+        let mut reader = ChecksumReader::new(inner, Crc32::ieee());
+        if reader.read_u16_be()? != 0x1f8b {
+            return Err(invalid("gzip", "invalid magic number"));
+        }
+        if reader.read_u8()? != 8 {
+            return Err(invalid("gzip", "unsupported compression method"));
+        }
+        let flags = reader.read_u8()?;
+        if flags & 0xe0 != 0 {
+            return Err(invalid("gzip", "reserved flags are set"));
+        }
+        let mtime = reader.read_u32_le()?;
+        let _extra_flags = reader.read_u8()?;
+        let os = reader.read_u8()?;
+
+        let extra = match flags & EXTRA != 0 {
+            true => {
+                let len = reader.read_u16_le()? as usize;
+                Some(reader.read_vec(len)?)
+            }
+            false => None,
+        };
+        let name = (flags & NAME != 0)
+            .then(|| read_zero_terminated(&mut reader))
+            .transpose()?;
+        let comment = (flags & COMMENT != 0)
+            .then(|| read_zero_terminated(&mut reader))
+            .transpose()?;
+
+        if flags & HEADER_CRC != 0 {
+            let actual = reader.value() as u16;
+            let expected = reader.read_u16_le()?;
+            if expected != actual {
+                return Err(Error::ChecksumMismatch {
+                    offset: None,
+                    type_name: "gzip header CRC",
+                    expected: expected as u64,
+                    actual: actual as u64,
+                });
+            }
+        }
+
+        Ok(Self {
+            mtime,
+            os,
+            text: flags & TEXT != 0,
+            extra,
+            name,
+            comment,
+        })
+    }
+
+    /// Fails if the extra field is longer than `u16::MAX` bytes.
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let flags = [
+            (self.text, TEXT),
+            (self.extra.is_some(), EXTRA),
+            (self.name.is_some(), NAME),
+            (self.comment.is_some(), COMMENT),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |flags, (_, flag)| flags | flag);
+
+        let mut bytes = vec![0x1f, 0x8b, 8, flags];
+        bytes.extend(self.mtime.to_le_bytes());
+        bytes.extend([0, self.os]);
+        if let Some(extra) = &self.extra {
+            let len = u16::try_from(extra.len()).map_err(|_| Error::OutOfRange {
+                offset: None,
+                value: extra.len() as i128,
+                type_name: "u16",
+            })?;
+            bytes.extend(len.to_le_bytes());
+            bytes.extend(extra);
+        }
+        for field in [&self.name, &self.comment].into_iter().flatten() {
+            bytes.extend(field);
+            bytes.push(0);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Wrapper for `Read` that decompresses a single gzip member and verifies its CRC-32 and size.
+pub struct GzipReader<R: Read> {
+    inflate: InflateReader<R>,
+    header: GzipHeader,
+    crc: Crc32,
+    size: u32,
+    verified: bool,
+}
+
+impl<R: Read> GzipReader<R> {
+    /// Reads and checks the gzip header, including its CRC-16 if present.
+    pub fn new(inner: R) -> Result<Self> {
+        Self::with_limit(inner, u64::MAX)
+    }
+
+    /// Like `new`, but fails once more than `limit` bytes are decompressed.
+    pub fn with_limit(mut inner: R, limit: u64) -> Result<Self> {
+        let header = GzipHeader::read(&mut inner)?;
+        Ok(Self {
+            inflate: InflateReader::with_limit(inner, limit),
+            header,
+            crc: Crc32::ieee(),
+            size: 0,
+            verified: false,
+        })
+    }
+
+    pub fn header(&self) -> &GzipHeader {
+        &self.header
+    }
+
+    pub fn get_ref(&self) -> &R {
+        self.inflate.get_ref()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inflate.into_inner()
+    }
+
+    fn verify(&mut self) -> Result<()> {
+        let inner = self.inflate.get_mut();
+        let (expected, size) = (inner.read_u32_le()?, inner.read_u32_le()?);
+        self.verified = true;
+        if expected != self.crc.value() {
+            return Err(Error::ChecksumMismatch {
+                offset: None,
+                type_name: "Crc32",
+                expected: expected as u64,
+                actual: self.crc.value() as u64,
+            });
+        }
+        match size == self.size {
+            true => Ok(()),
+            false => Err(invalid("gzip", "uncompressed size mismatch")),
+        }
+    }
+}
+
+impl<R: Read> Read for GzipReader<R> {
+    /// Decompresses data, the checksum and size are verified once the final block is read.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inflate.read(buf)?;
+        self.crc.update(&buf[..len]);
+        // the size is stored modulo 2^32
+        self.size = self.size.wrapping_add(len as u32);
+        if self.inflate.is_finished() && !self.verified {
+            self.verify()?;
+        }
+        Ok(len)
+    }
+}
+
+/// Wrapper for `Write` that compresses everything written to it as a gzip file.
+///
+/// Call `finish` to write the final block, the checksum and the size.
+pub struct GzipWriter<W: Write> {
+    deflate: DeflateWriter<W>,
+    header: Option<GzipHeader>,
+    crc: Crc32,
+    size: u32,
+}
+
+impl<W: Write> GzipWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_header(inner, GzipHeader::default())
+    }
+
+    pub fn with_header(inner: W, header: GzipHeader) -> Self {
+        Self {
+            deflate: DeflateWriter::new(inner),
+            header: Some(header),
+            crc: Crc32::ieee(),
+            size: 0,
+        }
+    }
+
+    /// Fails without writing anything if the header can't be stored.
+    fn write_header(&mut self) -> io::Result<()> {
+        if let Some(header) = &self.header {
+            self.deflate.get_mut().write_all(&header.to_bytes()?)?;
+            self.header = None;
+        }
+        Ok(())
+    }
+
+    /// Writes the final block, the checksum and the size and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_header()?;
+        let mut inner = self.deflate.finish()?;
+        inner.write_all(&self.crc.value().to_le_bytes())?;
+        inner.write_all(&self.size.to_le_bytes())?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for GzipWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_header()?;
+        let len = self.deflate.write(buf)?;
+        self.crc.update(&buf[..len]);
+        self.size = self.size.wrapping_add(len as u32);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.deflate.flush()
+    }
+}
@@ -0,0 +1,290 @@
+use super::{DIST_BASE, DIST_EXTRA, LENGTH_BASE, LENGTH_EXTRA, Output, invalid};
+use crate::{Error, Result};
+use std::io::{self, Read};
+
+/// Size of the sliding window, matches can reach back this far.
+const WINDOW: usize = 32768;
+
+/// Order in which the code length code lengths are stored in a dynamic block header.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Reads bits least significant bit first, one byte at a time so nothing after the stream is consumed.
+struct BitReader<R: Read> {
+    inner: R,
+    bits: u32,
+    count: u32,
+}
+
+impl<R: Read> BitReader<R> {
+    fn bits(&mut self, n: u32) -> Result<u32> {
+        while self.count < n {
+            let mut byte = [0];
+            self.inner
+                .read_exact(&mut byte)
+                .map_err(|e| Error::for_value(e, None, "deflate", 1))?;
+            self.bits |= (byte[0] as u32) << self.count;
+            self.count += 8;
+        }
+        let value = self.bits & ((1 << n) - 1);
+        self.bits >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Drops the bits left in the current byte.
+    fn align(&mut self) {
+        self.bits = 0;
+        self.count = 0;
+    }
+}
+
+/// Canonical Huffman code stored as the number of codes per length and the symbols sorted by code.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self> {
+        let mut counts = [0u16; 16];
+        for len in lengths {
+            counts[*len as usize] += 1;
+        }
+
+        // more codes than there is room for can't be decoded
+        let mut left = 1i32;
+        for count in &counts[1..] {
+            left = (left << 1) - *count as i32;
+            if left < 0 {
+                return Err(invalid("deflate", "over-subscribed Huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                symbols[offsets[*len as usize] as usize] = symbol as u16;
+                offsets[*len as usize] += 1;
+            }
+        }
+        counts[0] = 0;
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode<R: Read>(&self, input: &mut BitReader<R>) -> Result<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for count in &self.counts[1..] {
+            code |= input.bits(1)? as i32;
+            let count = *count as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid("deflate", "invalid Huffman code"))
+    }
+}
+
+enum State {
+    Header,
+    Stored(u16),
+    Codes(Box<(Huffman, Huffman)>),
+    Done,
+}
+
+/// Wrapper for `Read` that decompresses a raw DEFLATE stream (RFC 1951).
+///
+/// The compressed stream is read one byte at a time, so the inner reader is left
+/// right after the end of the stream. Wrap slow readers in a `BufReader` only if nothing follows the stream.
+pub struct InflateReader<R: Read> {
+    input: BitReader<R>,
+    state: State,
+    last: bool,
+    output: Output,
+    /// Error of decoding ahead, returned by the next read.
+    error: Option<Error>,
+}
+
+impl<R: Read> InflateReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_limit(inner, u64::MAX)
+    }
+
+    /// Creates a new reader that fails once more than `limit` bytes are decompressed.
+    pub fn with_limit(inner: R, limit: u64) -> Self {
+        Self {
+            input: BitReader {
+                inner,
+                bits: 0,
+                count: 0,
+            },
+            state: State::Header,
+            last: false,
+            output: Output::new(WINDOW, limit),
+            error: None,
+        }
+    }
+
+    /// Returns `true` once the final block was decompressed.
+    pub fn is_done(&self) -> bool {
+        matches!(self.state, State::Done)
+    }
+
+    /// Returns `true` once the final block was decompressed and read.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.is_done() && self.output.available() == 0 && self.error.is_none()
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.input.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// **Note:** reading from the inner reader before the stream is done corrupts the decompression.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.input.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.input.inner
+    }
+
+    fn read_header(&mut self) -> Result<State> {
+        self.last = self.input.bits(1)? == 1;
+        Ok(match self.input.bits(2)? {
+            0 => {
+                self.input.align();
+                let len = self.input.bits(16)? as u16;
+                if self.input.bits(16)? as u16 != !len {
+                    return Err(invalid("deflate", "stored block length mismatch"));
+                }
+                State::Stored(len)
+            }
+            1 => {
+                let mut lengths = [8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                State::Codes(Box::new((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?)))
+            }
+            2 => State::Codes(Box::new(self.read_dynamic()?)),
+            _ => return Err(invalid("deflate", "invalid block type")),
+        })
+    }
+
+    fn read_dynamic(&mut self) -> Result<(Huffman, Huffman)> {
+        let literals = self.input.bits(5)? as usize + 257;
+        let distances = self.input.bits(5)? as usize + 1;
+        let code_lengths = self.input.bits(4)? as usize + 4;
+        if literals > 286 || distances > 30 {
+            return Err(invalid("deflate", "too many codes"));
+        }
+
+        let mut lengths = [0; 19];
+        for index in &CODE_LENGTH_ORDER[..code_lengths] {
+            lengths[*index] = self.input.bits(3)? as u8;
+        }
+        let code = Huffman::new(&lengths)?;
+
+        let mut lengths = vec![0; literals + distances];
+        let mut index = 0;
+        while index < lengths.len() {
+            let symbol = code.decode(&mut self.input)?;
+            let (value, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 if index == 0 => return Err(invalid("deflate", "repeat without a length")),
+                16 => (lengths[index - 1], 3 + self.input.bits(2)? as usize),
+                17 => (0, 3 + self.input.bits(3)? as usize),
+                _ => (0, 11 + self.input.bits(7)? as usize),
+            };
+            if index + repeat > lengths.len() {
+                return Err(invalid("deflate", "too many code lengths"));
+            }
+            lengths[index..index + repeat].fill(value);
+            index += repeat;
+        }
+
+        if lengths[256] == 0 {
+            return Err(invalid("deflate", "missing end of block code"));
+        }
+        let (literals, distances) = lengths.split_at(literals);
+        Ok((Huffman::new(literals)?, Huffman::new(distances)?))
+    }
+
+    fn next_block(&self) -> State {
+        match self.last {
+            true => State::Done,
+            false => State::Header,
+        }
+    }
+
+    /// Decompresses until at least `want` bytes are available or the stream is done.
+    fn fill(&mut self, want: usize) -> Result<()> {
+        while self.output.available() < want {
+            self.state = match std::mem::replace(&mut self.state, State::Done) {
+                State::Done => return Ok(()),
+                State::Header => self.read_header()?,
+                State::Stored(len) => {
+                    self.output
+                        .read_from(&mut self.input.inner, len as usize, "deflate")?;
+                    self.next_block()
+                }
+                State::Codes(codes) => match self.decode_symbol(&codes)? {
+                    true => State::Codes(codes),
+                    false => self.next_block(),
+                },
+            };
+        }
+        Ok(())
+    }
+
+    /// Decodes a literal or a match, returns `false` at the end of the block.
+    fn decode_symbol(&mut self, codes: &(Huffman, Huffman)) -> Result<bool> {
+        let symbol = codes.0.decode(&mut self.input)? as usize;
+        match symbol {
+            0..256 => self.output.push(symbol as u8, 1)?,
+            256 => return Ok(false),
+            257..286 => {
+                let symbol = symbol - 257;
+                let len =
+                    LENGTH_BASE[symbol] as usize + self.input.bits(LENGTH_EXTRA[symbol])? as usize;
+                let symbol = codes.1.decode(&mut self.input)? as usize;
+                if symbol >= 30 {
+                    return Err(invalid("deflate", "invalid distance code"));
+                }
+                let dist =
+                    DIST_BASE[symbol] as usize + self.input.bits(DIST_EXTRA[symbol])? as usize;
+                self.output.copy_match(dist, len, "deflate")?;
+            }
+            _ => return Err(invalid("deflate", "invalid length code")),
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for InflateReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(error) = self.error.take() {
+            return Err(error.into());
+        }
+        self.fill(buf.len().min(WINDOW))?;
+        let len = self.output.read(buf);
+        // decode ahead, so the end of the stream is noticed without another read,
+        // an error is kept for the next read to not lose the bytes decoded already
+        if self.output.available() == 0 {
+            match self.fill(1) {
+                Err(error) if len > 0 => self.error = Some(error),
+                result => result?,
+            }
+        }
+        Ok(len)
+    }
+}
@@ -0,0 +1,164 @@
+use super::{Output, matcher::Matcher, read_byte_opt};
+use crate::{Error, ReadVal, Result};
+use std::io::{self, Read, Write};
+
+const MIN_MATCH: usize = 4;
+const MAX_DIST: usize = 65535;
+/// The last 5 bytes are always literals.
+const LAST_LITERALS: usize = 5;
+/// The last match starts at least 12 bytes before the end.
+const MATCH_LIMIT: usize = 12;
+
+/// Reads the extension bytes of a literal or match length.
+fn read_len<R: Read>(reader: &mut R, nibble: u8) -> Result<usize> {
+    let mut len = nibble as usize;
+    if nibble == 15 {
+        loop {
+            let byte = reader
+                .read_u8()
+                .map_err(|e| Error::for_value(e, None, "lz4 block", 1))?;
+            len += byte as usize;
+            if byte != 255 {
+                break;
+            }
+        }
+    }
+    Ok(len)
+}
+
+fn put_len(out: &mut Vec<u8>, len: usize) {
+    if len >= 15 {
+        let mut left = len - 15;
+        while left >= 255 {
+            out.push(255);
+            left -= 255;
+        }
+        out.push(left as u8);
+    }
+}
+
+/// Wrapper for `Read` that decompresses an LZ4 block (without the frame format).
+///
+/// The block ends with the inner reader, use `Read::take` if it is followed by other data.
+pub struct Lz4BlockReader<R: Read> {
+    inner: R,
+    output: Output,
+}
+
+impl<R: Read> Lz4BlockReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_limit(inner, u64::MAX)
+    }
+
+    /// Creates a new reader that fails once more than `limit` bytes are decompressed.
+    pub fn with_limit(inner: R, limit: u64) -> Self {
+        Self {
+            inner,
+            output: Output::new(MAX_DIST, limit),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Decodes one sequence, returns `false` at the end of the block.
+    fn sequence(&mut self) -> Result<bool> {
+        let Some(token) = read_byte_opt(&mut self.inner)? else {
+            return Ok(false);
+        };
+        let literals = read_len(&mut self.inner, token >> 4)?;
+        self.output
+            .read_from(&mut self.inner, literals, "lz4 block")?;
+
+        // the last sequence only has literals
+        let Some(low) = read_byte_opt(&mut self.inner)? else {
+            return Ok(false);
+        };
+        let high = self
+            .inner
+            .read_u8()
+            .map_err(|e| Error::for_value(e, None, "lz4 block", 1))?;
+        let dist = u16::from_le_bytes([low, high]) as usize;
+        let len = read_len(&mut self.inner, token & 0xf)? + MIN_MATCH;
+        self.output.copy_match(dist, len, "lz4 block")?;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for Lz4BlockReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.output.available() == 0 && !buf.is_empty() {
+            if !self.sequence()? {
+                break;
+            }
+        }
+        Ok(self.output.read(buf))
+    }
+}
+
+/// Wrapper for `Write` that compresses everything written to it as one LZ4 block.
+///
+/// A block can only be compressed as a whole, so the data is buffered until `finish` is called.
+pub struct Lz4BlockWriter<W: Write> {
+    inner: W,
+    data: Vec<u8>,
+}
+
+impl<W: Write> Lz4BlockWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            data: Vec::new(),
+        }
+    }
+
+    fn put_sequence(out: &mut Vec<u8>, literals: &[u8], dist: usize, len: usize) {
+        let match_len = len.saturating_sub(MIN_MATCH);
+        let token = (literals.len().min(15) as u8) << 4 | match_len.min(15) as u8;
+        out.push(token);
+        put_len(out, literals.len());
+        out.extend_from_slice(literals);
+        if len > 0 {
+            out.extend((dist as u16).to_le_bytes());
+            put_len(out, match_len);
+        }
+    }
+
+    /// Compresses the buffered data, writes the block and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let data = &self.data;
+        let mut matcher = Matcher::new(data, MIN_MATCH);
+        let mut out = Vec::new();
+        let (mut anchor, mut pos) = (0, 0);
+        while pos + MATCH_LIMIT <= data.len() {
+            let (len, dist) = matcher.find(pos, MAX_DIST, data.len() - LAST_LITERALS - pos);
+            if len == 0 {
+                matcher.insert(pos);
+                pos += 1;
+                continue;
+            }
+            Self::put_sequence(&mut out, &data[anchor..pos], dist, len);
+            for pos in pos..pos + len {
+                matcher.insert(pos);
+            }
+            pos += len;
+            anchor = pos;
+        }
+        Self::put_sequence(&mut out, &data[anchor..], 0, 0);
+
+        self.inner.write_all(&out)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for Lz4BlockWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
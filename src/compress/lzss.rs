@@ -0,0 +1,269 @@
+use super::{Output, invalid, matcher::Matcher, read_byte_opt};
+use crate::{Error, ReadVal, Result};
+use std::io::{self, Read, Write};
+
+const WINDOW: usize = 4096;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 18;
+
+/// How LZSS references to earlier data are stored, both use two bytes with a 12-bit position and a 4-bit length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reference {
+    /// Absolute position in a ring buffer that starts at `start` and is prefilled with the fill byte.
+    ///
+    /// The first byte holds the low 8 bits of the position, the second the high 4 bits
+    /// in its high nibble and the length minus 3 in its low nibble (Okumura's `LZSS.C`).
+    Absolute { start: u16 },
+    /// Distance back from the current position.
+    ///
+    /// Stored big endian with the length minus 3 in the high nibble and the distance minus 1 in the low 12 bits (Nintendo LZ10).
+    Relative,
+}
+
+/// Conventions of an LZSS stream, which differ between the many LZSS variants.
+///
+/// All variants use a 4 KiB window, 8 flags per flag byte and matches of 3 to 18 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LzssFormat {
+    pub reference: Reference,
+    /// Whether the flags are read from the most significant bit first.
+    pub msb_first: bool,
+    /// Flag value that marks a literal byte.
+    pub literal_flag: bool,
+    /// Byte the ring buffer is prefilled with for absolute references.
+    pub fill: u8,
+}
+
+impl LzssFormat {
+    /// Haruhiko Okumura's `LZSS.C`, used by many games and old archivers.
+    pub const OKUMURA: Self = Self {
+        reference: Reference::Absolute { start: 0xfee },
+        msb_first: false,
+        literal_flag: true,
+        fill: b' ',
+    };
+
+    /// Nintendo LZ10 as used on the GBA and DS, without the 4 byte header.
+    pub const LZ10: Self = Self {
+        reference: Reference::Relative,
+        msb_first: true,
+        literal_flag: false,
+        fill: 0,
+    };
+
+    /// Returns the distance back from the ring buffer position `pos` (1 to 4096) and the length.
+    fn decode_reference(self, bytes: [u8; 2], pos: usize) -> (usize, usize) {
+        match self.reference {
+            Reference::Absolute { .. } => {
+                let target = bytes[0] as usize | (bytes[1] as usize >> 4) << 8;
+                let len = (bytes[1] & 0xf) as usize + MIN_MATCH;
+                ((pos + WINDOW - target - 1) % WINDOW + 1, len)
+            }
+            Reference::Relative => {
+                let dist = (u16::from_be_bytes(bytes) & 0xfff) as usize + 1;
+                (dist, (bytes[0] >> 4) as usize + MIN_MATCH)
+            }
+        }
+    }
+
+    fn encode_reference(self, dist: usize, len: usize, pos: usize) -> [u8; 2] {
+        let len = (len - MIN_MATCH) as u16;
+        match self.reference {
+            Reference::Absolute { .. } => {
+                let target = ((pos + WINDOW - dist) % WINDOW) as u16;
+                [target as u8, (target >> 8 << 4 | len) as u8]
+            }
+            Reference::Relative => (len << 12 | (dist - 1) as u16).to_be_bytes(),
+        }
+    }
+
+    fn start(self) -> usize {
+        match self.reference {
+            Reference::Absolute { start } => start as usize % WINDOW,
+            Reference::Relative => 0,
+        }
+    }
+}
+
+/// Wrapper for `Read` that decompresses an LZSS stream.
+///
+/// The stream ends with the inner reader, use `Read::take` if it is followed by other data.
+pub struct LzssReader<R: Read> {
+    inner: R,
+    format: LzssFormat,
+    ring: Box<[u8; WINDOW]>,
+    pos: usize,
+    written: u64,
+    flags: u8,
+    remaining: u8,
+    output: Output,
+}
+
+impl<R: Read> LzssReader<R> {
+    pub fn new(inner: R, format: LzssFormat) -> Self {
+        Self::with_limit(inner, format, u64::MAX)
+    }
+
+    /// Creates a new reader that fails once more than `limit` bytes are decompressed.
+    pub fn with_limit(inner: R, format: LzssFormat, limit: u64) -> Self {
+        Self {
+            inner,
+            format,
+            ring: Box::new([format.fill; WINDOW]),
+            pos: format.start(),
+            written: 0,
+            flags: 0,
+            remaining: 0,
+            output: Output::new(0, limit),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn put(&mut self, byte: u8) {
+        self.ring[self.pos] = byte;
+        self.pos = (self.pos + 1) % WINDOW;
+        self.written += 1;
+    }
+
+    /// Decodes one literal or reference, returns `false` at the end of the stream.
+    fn item(&mut self) -> Result<bool> {
+        if self.remaining == 0 {
+            match read_byte_opt(&mut self.inner)? {
+                Some(flags) => self.flags = flags,
+                None => return Ok(false),
+            }
+            self.remaining = 8;
+        }
+        let flag = match self.format.msb_first {
+            true => self.flags & 0x80 != 0,
+            false => self.flags & 1 != 0,
+        };
+        self.flags = if self.format.msb_first {
+            self.flags << 1
+        } else {
+            self.flags >> 1
+        };
+        self.remaining -= 1;
+
+        // the last flag byte is usually not filled completely
+        let Some(first) = read_byte_opt(&mut self.inner)? else {
+            return Ok(false);
+        };
+        if flag == self.format.literal_flag {
+            self.output.push(first, 1)?;
+            self.put(first);
+            return Ok(true);
+        }
+
+        let second = self
+            .inner
+            .read_u8()
+            .map_err(|e| Error::for_value(e, None, "lzss", 1))?;
+        let (dist, len) = self.format.decode_reference([first, second], self.pos);
+        // relative references can't reach the prefilled part of the ring buffer
+        if self.format.reference == Reference::Relative && dist as u64 > self.written {
+            return Err(invalid("lzss", "match distance out of range"));
+        }
+        self.output.reserve(len)?;
+        for _ in 0..len {
+            let byte = self.ring[(self.pos + WINDOW - dist) % WINDOW];
+            self.output.buf.push(byte);
+            self.put(byte);
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for LzssReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.output.available() == 0 && !buf.is_empty() {
+            if !self.item()? {
+                break;
+            }
+        }
+        Ok(self.output.read(buf))
+    }
+}
+
+/// Wrapper for `Write` that compresses everything written to it as LZSS.
+///
+/// The data is buffered and compressed when `finish` is called. References only point to
+/// the written data, never to the prefilled part of the ring buffer.
+pub struct LzssWriter<W: Write> {
+    inner: W,
+    format: LzssFormat,
+    data: Vec<u8>,
+}
+
+impl<W: Write> LzssWriter<W> {
+    pub fn new(inner: W, format: LzssFormat) -> Self {
+        Self {
+            inner,
+            format,
+            data: Vec::new(),
+        }
+    }
+
+    /// Compresses the buffered data, writes it and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let data = &self.data;
+        let mut matcher = Matcher::new(data, MIN_MATCH);
+        let mut out = Vec::new();
+        let mut group = Vec::with_capacity(17);
+        let mut flags = 0u8;
+        let mut count = 0;
+        let mut pos = 0;
+
+        while pos < data.len() {
+            // stay clear of the bytes that are being overwritten in the ring buffer
+            let (len, dist) = matcher.find(pos, WINDOW - MAX_MATCH, MAX_MATCH);
+            let literal = len == 0;
+            if literal == self.format.literal_flag {
+                flags |= match self.format.msb_first {
+                    true => 0x80 >> count,
+                    false => 1 << count,
+                };
+            }
+            match literal {
+                true => {
+                    group.push(data[pos]);
+                    matcher.insert(pos);
+                    pos += 1;
+                }
+                false => {
+                    let ring_pos = (self.format.start() + pos) % WINDOW;
+                    group.extend(self.format.encode_reference(dist, len, ring_pos));
+                    for pos in pos..pos + len {
+                        matcher.insert(pos);
+                    }
+                    pos += len;
+                }
+            }
+
+            count += 1;
+            if count == 8 || pos == data.len() {
+                out.push(flags);
+                out.append(&mut group);
+                flags = 0;
+                count = 0;
+            }
+        }
+
+        self.inner.write_all(&out)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for LzssWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
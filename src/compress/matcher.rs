@@ -0,0 +1,70 @@
+const HASH_BITS: u32 = 16;
+const MAX_CHAIN: usize = 64;
+const NONE: usize = usize::MAX;
+
+/// Finds earlier occurrences of the bytes at a position using hash chains over a whole buffer.
+pub(super) struct Matcher<'a> {
+    data: &'a [u8],
+    min: usize,
+    head: Vec<usize>,
+    prev: Vec<usize>,
+}
+
+impl<'a> Matcher<'a> {
+    /// Creates a matcher for matches of at least `min` (3 or 4) bytes.
+    pub(super) fn new(data: &'a [u8], min: usize) -> Self {
+        Self {
+            data,
+            min,
+            head: vec![NONE; 1 << HASH_BITS],
+            prev: vec![NONE; data.len()],
+        }
+    }
+
+    fn hash(&self, pos: usize) -> usize {
+        let value = self.data[pos..pos + self.min]
+            .iter()
+            .fold(0u32, |value, byte| value << 8 | *byte as u32);
+        (value.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+    }
+
+    pub(super) fn insert(&mut self, pos: usize) {
+        if pos + self.min <= self.data.len() {
+            let hash = self.hash(pos);
+            self.prev[pos] = self.head[hash];
+            self.head[hash] = pos;
+        }
+    }
+
+    /// Returns the length and distance of the longest match, the length is 0 if there is none.
+    pub(super) fn find(&self, pos: usize, max_dist: usize, max_len: usize) -> (usize, usize) {
+        let max_len = max_len.min(self.data.len() - pos);
+        if max_len < self.min {
+            return (0, 0);
+        }
+
+        let mut best = (0, 0);
+        let mut candidate = self.head[self.hash(pos)];
+        for _ in 0..MAX_CHAIN {
+            if candidate == NONE || pos - candidate > max_dist {
+                break;
+            }
+            let len = self.data[candidate..]
+                .iter()
+                .zip(&self.data[pos..pos + max_len])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len > best.0 {
+                best = (len, pos - candidate);
+                if len == max_len {
+                    break;
+                }
+            }
+            candidate = self.prev[candidate];
+        }
+        match best.0 >= self.min {
+            true => best,
+            false => (0, 0),
+        }
+    }
+}
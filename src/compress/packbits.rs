@@ -0,0 +1,126 @@
+use super::{Output, read_byte_opt};
+use crate::{Error, ReadVal};
+use std::io::{self, Read, Write};
+
+/// Wrapper for `Read` that decodes PackBits as used by TIFF, PSD and MacPaint.
+pub struct PackBitsReader<R: Read> {
+    inner: R,
+    output: Output,
+}
+
+impl<R: Read> PackBitsReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_limit(inner, u64::MAX)
+    }
+
+    /// Creates a new reader that fails once more than `limit` bytes are decoded.
+    pub fn with_limit(inner: R, limit: u64) -> Self {
+        Self {
+            inner,
+            output: Output::new(0, limit),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for PackBitsReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.output.available() == 0 && !buf.is_empty() {
+            let header = match read_byte_opt(&mut self.inner)? {
+                None => return Ok(0),
+                Some(header) => header as i8,
+            };
+            match header {
+                0.. => {
+                    let len = header as usize + 1;
+                    self.output.read_from(&mut self.inner, len, "packbits")?;
+                }
+                // no-op, some encoders use it as padding
+                -128 => {}
+                _ => {
+                    let value = self
+                        .inner
+                        .read_u8()
+                        .map_err(|e| Error::for_value(e, None, "packbits", 1))?;
+                    self.output.push(value, (1 - header as isize) as usize)?;
+                }
+            }
+        }
+        Ok(self.output.read(buf))
+    }
+}
+
+/// Wrapper for `Write` that encodes everything written to it as PackBits.
+///
+/// Runs of 3 or more bytes are stored as repeats, everything else as literals.
+/// Call `finish` to write the pending bytes.
+pub struct PackBitsWriter<W: Write> {
+    inner: W,
+    literal: Vec<u8>,
+    run: Option<(u8, usize)>,
+}
+
+impl<W: Write> PackBitsWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            literal: Vec::new(),
+            run: None,
+        }
+    }
+
+    fn put_literal(&mut self, out: &mut Vec<u8>, all: bool) {
+        while self.literal.len() >= 128 || (all && !self.literal.is_empty()) {
+            let chunk: Vec<u8> = self.literal.drain(..self.literal.len().min(128)).collect();
+            out.push(chunk.len() as u8 - 1);
+            out.extend(chunk);
+        }
+    }
+
+    fn put_run(&mut self, out: &mut Vec<u8>) {
+        match self.run.take() {
+            Some((value, count)) if count >= 3 => {
+                self.put_literal(out, true);
+                out.extend([(1 - count as isize) as u8, value]);
+            }
+            Some((value, count)) => {
+                self.literal.resize(self.literal.len() + count, value);
+                self.put_literal(out, false);
+            }
+            None => {}
+        }
+    }
+
+    /// Writes the pending bytes and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut out = Vec::new();
+        self.put_run(&mut out);
+        self.put_literal(&mut out, true);
+        self.inner.write_all(&out)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for PackBitsWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut out = Vec::new();
+        for byte in buf {
+            match &mut self.run {
+                Some((value, count)) if value == byte && *count < 128 => *count += 1,
+                _ => {
+                    self.put_run(&mut out);
+                    self.run = Some((*byte, 1));
+                }
+            }
+        }
+        self.inner.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
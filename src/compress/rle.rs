@@ -0,0 +1,92 @@
+use super::{Output, invalid, read_byte_opt};
+use crate::{Error, ReadVal};
+use std::io::{self, Read, Write};
+
+/// Wrapper for `Read` that decodes simple run-length encoding.
+///
+/// Each run is stored as a count from 1 to 255 followed by the byte to repeat.
+pub struct RleReader<R: Read> {
+    inner: R,
+    output: Output,
+}
+
+impl<R: Read> RleReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_limit(inner, u64::MAX)
+    }
+
+    /// Creates a new reader that fails once more than `limit` bytes are decoded.
+    pub fn with_limit(inner: R, limit: u64) -> Self {
+        Self {
+            inner,
+            output: Output::new(0, limit),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for RleReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.output.available() == 0 && !buf.is_empty() {
+            let count = match read_byte_opt(&mut self.inner)? {
+                None => return Ok(0),
+                Some(0) => return Err(invalid("rle", "zero run length").into()),
+                Some(count) => count,
+            };
+            let value = self
+                .inner
+                .read_u8()
+                .map_err(|e| Error::for_value(e, None, "rle", 1))?;
+            self.output.push(value, count as usize)?;
+        }
+        Ok(self.output.read(buf))
+    }
+}
+
+/// Wrapper for `Write` that run-length encodes everything written to it.
+///
+/// Call `finish` to write the last run.
+pub struct RleWriter<W: Write> {
+    inner: W,
+    run: Option<(u8, u8)>,
+}
+
+impl<W: Write> RleWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, run: None }
+    }
+
+    /// Writes the last run and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some((value, count)) = self.run {
+            self.inner.write_all(&[count, value])?;
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for RleWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut out = Vec::new();
+        for byte in buf {
+            self.run = match self.run {
+                Some((value, count)) if value == *byte && count < 255 => Some((value, count + 1)),
+                run => {
+                    if let Some((value, count)) = run {
+                        out.extend([count, value]);
+                    }
+                    Some((*byte, 1))
+                }
+            };
+        }
+        self.inner.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,124 @@
+use super::{DeflateWriter, InflateReader, invalid};
+use crate::{
+    Error, ReadVal, Result,
+    checksum::{Adler32, Checksum},
+};
+use std::io::{self, Read, Write};
+
+/// Wrapper for `Read` that decompresses a zlib stream and verifies its Adler-32 checksum.
+pub struct ZlibReader<R: Read> {
+    inflate: InflateReader<R>,
+    adler: Adler32,
+    verified: bool,
+}
+
+impl<R: Read> ZlibReader<R> {
+    /// Reads and checks the zlib header, preset dictionaries are not supported.
+    pub fn new(inner: R) -> Result<Self> {
+        Self::with_limit(inner, u64::MAX)
+    }
+
+    /// Like `new`, but fails once more than `limit` bytes are decompressed.
+    pub fn with_limit(mut inner: R, limit: u64) -> Result<Self> {
+        let header = inner.read_u16_be()?;
+        if header >> 8 & 0xf != 8 || header >> 12 > 7 {
+            return Err(invalid("zlib", "unsupported compression method"));
+        }
+        if header % 31 != 0 {
+            return Err(invalid("zlib", "header check failed"));
+        }
+        if header & 0x20 != 0 {
+            return Err(invalid("zlib", "preset dictionaries are not supported"));
+        }
+
+        Ok(Self {
+            inflate: InflateReader::with_limit(inner, limit),
+            adler: Adler32::new(),
+            verified: false,
+        })
+    }
+
+    pub fn get_ref(&self) -> &R {
+        self.inflate.get_ref()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inflate.into_inner()
+    }
+
+    fn verify(&mut self) -> Result<()> {
+        let expected = self.inflate.get_mut().read_u32_be()?;
+        let actual = self.adler.value();
+        self.verified = true;
+        match expected == actual {
+            true => Ok(()),
+            false => Err(Error::ChecksumMismatch {
+                offset: None,
+                type_name: "Adler32",
+                expected: expected as u64,
+                actual: actual as u64,
+            }),
+        }
+    }
+}
+
+impl<R: Read> Read for ZlibReader<R> {
+    /// Decompresses data, the checksum is verified once the final block is read.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inflate.read(buf)?;
+        self.adler.update(&buf[..len]);
+        if self.inflate.is_finished() && !self.verified {
+            self.verify()?;
+        }
+        Ok(len)
+    }
+}
+
+/// Wrapper for `Write` that compresses everything written to it as a zlib stream.
+///
+/// Call `finish` to write the final block and the checksum.
+pub struct ZlibWriter<W: Write> {
+    deflate: DeflateWriter<W>,
+    adler: Adler32,
+    header_written: bool,
+}
+
+impl<W: Write> ZlibWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            deflate: DeflateWriter::new(inner),
+            adler: Adler32::new(),
+            header_written: false,
+        }
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.header_written {
+            // deflate with a 32 KiB window, fastest compression level
+            self.deflate.get_mut().write_all(&[0x78, 0x01])?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+
+    /// Writes the final block and the checksum and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_header()?;
+        let mut inner = self.deflate.finish()?;
+        inner.write_all(&self.adler.value().to_be_bytes())?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for ZlibWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_header()?;
+        let len = self.deflate.write(buf)?;
+        self.adler.update(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.deflate.flush()
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::VecDeque;
+use std::io::{
+    Error,
+    ErrorKind::{Interrupted, InvalidInput, UnexpectedEof, Unsupported, WriteZero},
+    Read, Result, Seek, SeekFrom, Write,
+};
+
+const DEFAULT_WINDOW: usize = 8192;
+
+/// Records the position where a read or write started in its error, so `dh::Error::offset` knows it.
+fn at_offset(error: Error, offset: u64) -> Error {
+    crate::Error::Io {
+        source: error,
+        offset: Some(offset),
+    }
+    .into()
+}
+
+/// Wrapper for `Read` that tracks the stream position and allows seeking on non-seekable streams.
+///
+/// Seeking forward skips bytes by reading them. The last `window` bytes are kept,
+/// so seeking back into them works as well, which makes `ReadValAt` usable on pipes and sockets
+/// as long as the accessed positions are not too far apart.
+///
+/// Errors of `read_exact`, and with it of all `ReadVal` methods, carry the position of the failed read.
+///
+/// The first read after seeking forward keeps the bytes from the previous position onward
+/// if it is at most `window` bytes ahead of it, so `ReadValAt` can seek back after reading ahead.
+/// Further ahead, the skipped bytes are discarded.
+pub struct CountingReader<R: Read> {
+    inner: R,
+    history: VecDeque<u8>,
+    inner_pos: u64,
+    pos: u64,
+    window: usize,
+    /// Position before the last forward seek, kept until the next read is done.
+    anchor: Option<u64>,
+}
+
+impl<R: Read> CountingReader<R> {
+    /// Creates a new reader that keeps the last 8 KiB for seeking back.
+    pub fn new(inner: R) -> Self {
+        Self::with_window(inner, DEFAULT_WINDOW)
+    }
+
+    /// Creates a new reader that keeps the last `window` bytes for seeking back.
+    pub fn with_window(inner: R, window: usize) -> Self {
+        Self {
+            inner,
+            history: VecDeque::new(),
+            inner_pos: 0,
+            pos: 0,
+            window,
+            anchor: None,
+        }
+    }
+
+    /// Returns the number of bytes read (or skipped) so far.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the inner reader.
+    ///
+    /// **Note:** the inner reader may be ahead of the tracked position by the bytes read ahead.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// **Note:** reading from the inner reader directly makes the tracked position incorrect.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, dropping all bytes that were read ahead but not consumed yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn history_start(&self) -> u64 {
+        self.inner_pos - self.history.len() as u64
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.history.extend(&buf[..read]);
+        self.inner_pos += read as u64;
+
+        let keep = self.anchor.map_or(self.pos, |anchor| anchor.min(self.pos));
+        while self.history.len() > self.window && self.history_start() < keep {
+            self.history.pop_front();
+        }
+        Ok(read)
+    }
+
+    fn skip_to_pos(&mut self) -> Result<bool> {
+        let mut buf = [0; 4096];
+        while self.inner_pos < self.pos {
+            let len = buf.len().min((self.pos - self.inner_pos) as usize);
+            if self.fill(&mut buf[..len])? == 0 {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> CountingReader<R> {
+    fn read_some(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if !self.skip_to_pos()? {
+            return Ok(0);
+        }
+
+        let read = if self.pos < self.inner_pos {
+            let start = (self.pos - self.history_start()) as usize;
+            let mut len = 0;
+            for (target, byte) in buf.iter_mut().zip(self.history.range(start..)) {
+                *target = *byte;
+                len += 1;
+            }
+            len
+        } else {
+            self.fill(buf)?
+        };
+
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let Some(anchor) = self.anchor else {
+            return self.read_some(buf);
+        };
+        if self.pos - anchor > self.window as u64 {
+            self.anchor = None;
+            return self.read_some(buf);
+        }
+
+        // fill the whole buffer, so a value read ahead doesn't push the previous position out of the window
+        let mut read = 0;
+        let result = loop {
+            if read == buf.len() {
+                break Ok(read);
+            }
+            match self.read_some(&mut buf[read..]) {
+                Ok(0) => break Ok(read),
+                Ok(len) => read += len,
+                Err(e) if e.kind() == Interrupted => {}
+                Err(_) if read > 0 => break Ok(read),
+                Err(e) => break Err(e),
+            }
+        };
+        self.anchor = None;
+        result
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let start = self.pos;
+        let mut read = 0;
+        while read < buf.len() {
+            match self.read(&mut buf[read..]) {
+                Ok(0) => return Err(at_offset(UnexpectedEof.into(), start)),
+                Ok(len) => read += len,
+                Err(e) if e.kind() == Interrupted => {}
+                Err(e) => return Err(at_offset(e, start)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(_) => {
+                return Err(Error::new(
+                    Unsupported,
+                    "Cannot seek relative to the end of a non-seekable stream",
+                ));
+            }
+        }
+        .ok_or_else(|| Error::new(InvalidInput, "Invalid seek to a negative position"))?;
+
+        if target < self.history_start() {
+            return Err(Error::new(
+                Unsupported,
+                "Cannot seek back beyond the buffered window",
+            ));
+        }
+
+        self.anchor = (target > self.pos).then_some(self.pos);
+        self.pos = target;
+        Ok(target)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// Wrapper for `Write` that tracks the stream position and allows seeking forward on non-seekable streams.
+///
+/// Seeking forward pads the skipped bytes with zeros, seeking back is not supported.
+/// Errors of `write_all`, and with it of all `WriteVal` methods, carry the position of the failed write.
+pub struct CountingWriter<W: Write> {
+    inner: W,
+    pos: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Returns the number of bytes written (or padded) so far.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    ///
+    /// **Note:** writing to the inner writer directly makes the tracked position incorrect.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.pos += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        let start = self.pos;
+        while !buf.is_empty() {
+            match self.write(buf) {
+                Ok(0) => return Err(at_offset(WriteZero.into(), start)),
+                Ok(len) => buf = &buf[len..],
+                Err(e) if e.kind() == Interrupted => {}
+                Err(e) => return Err(at_offset(e, start)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Seek for CountingWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) | SeekFrom::End(offset) => {
+                self.pos.checked_add_signed(offset)
+            }
+        }
+        .ok_or_else(|| Error::new(InvalidInput, "Invalid seek to a negative position"))?;
+
+        if target < self.pos {
+            return Err(Error::new(
+                Unsupported,
+                "Cannot seek back on a non-seekable stream",
+            ));
+        }
+
+        let zeros = [0; 4096];
+        while self.pos < target {
+            let len = zeros.len().min((target - self.pos) as usize);
+            self.write_all(&zeros[..len])?;
+        }
+        Ok(target)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
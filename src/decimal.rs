@@ -0,0 +1,165 @@
+use crate::{Error, Result, limit::read_bytes};
+use std::io::{self, ErrorKind::InvalidInput, Read, Write};
+
+/// Longest field that can be converted to an integer, 19 bytes hold at most 38 digits.
+const MAX_INT_BYTES: usize = 19;
+
+fn check_len(len: usize, min: usize, max: usize) -> Result<()> {
+    match len {
+        _ if len < min => Err(io::Error::new(InvalidInput, "Decimal field is too short").into()),
+        _ if len > max => Err(io::Error::new(
+            InvalidInput,
+            "Decimal fields read as integers can be at most 19 bytes long",
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+fn read_field<R: Read + ?Sized>(
+    reader: &mut R,
+    len: usize,
+    type_name: &'static str,
+) -> Result<Vec<u8>> {
+    read_bytes(reader, len).map_err(|e| Error::for_value(e, None, type_name, len))
+}
+
+fn invalid(type_name: &'static str, reason: &'static str) -> Error {
+    Error::InvalidEncoding {
+        offset: None,
+        type_name,
+        reason,
+    }
+}
+
+/// Splits BCD bytes into their digits, high nibble first.
+fn bcd_digits(bytes: &[u8]) -> Result<Vec<u8>> {
+    bytes
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0xf])
+        .map(|digit| match digit {
+            0..=9 => Ok(digit),
+            _ => Err(invalid("bcd", "invalid digit nibble")),
+        })
+        .collect()
+}
+
+/// Splits packed decimal bytes into their digits and the sign, which is the last nibble.
+///
+/// The sign nibbles `C`, `A`, `E` and `F` (unsigned) are positive, `D` and `B` are negative.
+fn packed_digits(bytes: &[u8]) -> Result<(bool, Vec<u8>)> {
+    let (last, bytes) = bytes.split_last().unwrap();
+    let negative = match last & 0xf {
+        0xa | 0xc | 0xe | 0xf => false,
+        0xb | 0xd => true,
+        _ => return Err(invalid("packed decimal", "invalid sign nibble")),
+    };
+
+    let mut digits =
+        bcd_digits(bytes).map_err(|_| invalid("packed decimal", "invalid digit nibble"))?;
+    match last >> 4 {
+        digit @ 0..=9 => digits.push(digit),
+        _ => return Err(invalid("packed decimal", "invalid digit nibble")),
+    }
+    Ok((negative, digits))
+}
+
+fn to_int(digits: &[u8]) -> u128 {
+    digits
+        .iter()
+        .fold(0, |value, digit| value * 10 + *digit as u128)
+}
+
+fn to_str(negative: bool, digits: &[u8]) -> String {
+    let start = digits
+        .iter()
+        .position(|digit| *digit != 0)
+        .unwrap_or(digits.len() - 1);
+    let sign = if negative { "-" } else { "" };
+    let digits = digits[start..].iter().map(|digit| (b'0' + digit) as char);
+    sign.chars().chain(digits).collect()
+}
+
+/// Fills `len` bytes with the digits of `value`, leaving `reserved` nibbles at the end.
+fn to_nibbles(value: u128, len: usize, reserved: usize) -> Option<Vec<u8>> {
+    let mut nibbles = vec![0; len * 2];
+    let mut value = value;
+    for nibble in nibbles[..len * 2 - reserved].iter_mut().rev() {
+        *nibble = (value % 10) as u8;
+        value /= 10;
+    }
+    (value == 0).then_some(nibbles)
+}
+
+fn write_field<W: Write + ?Sized>(
+    writer: &mut W,
+    nibbles: &[u8],
+    type_name: &'static str,
+) -> Result<()> {
+    let bytes: Vec<u8> = nibbles
+        .chunks_exact(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect();
+    writer
+        .write_all(&bytes)
+        .map_err(|e| Error::for_value(e, None, type_name, bytes.len()))
+}
+
+/// Reads a binary-coded decimal field as an integer.
+pub(crate) fn read_bcd<R: Read + ?Sized>(reader: &mut R, len: usize) -> Result<u128> {
+    check_len(len, 0, MAX_INT_BYTES)?;
+    Ok(to_int(&bcd_digits(&read_field(reader, len, "bcd")?)?))
+}
+
+/// Reads a binary-coded decimal field of any length as a string.
+pub(crate) fn read_bcd_str<R: Read + ?Sized>(reader: &mut R, len: usize) -> Result<String> {
+    let digits = bcd_digits(&read_field(reader, len, "bcd")?)?;
+    match digits.is_empty() {
+        true => Ok(String::new()),
+        false => Ok(to_str(false, &digits)),
+    }
+}
+
+/// Writes an integer as a binary-coded decimal field.
+pub(crate) fn write_bcd<W: Write + ?Sized>(writer: &mut W, len: usize, value: u128) -> Result<()> {
+    check_len(len, 0, MAX_INT_BYTES)?;
+    let nibbles = to_nibbles(value, len, 0).ok_or(Error::OutOfRange {
+        offset: None,
+        value: i128::try_from(value).unwrap_or(i128::MAX),
+        type_name: "bcd",
+    })?;
+    write_field(writer, &nibbles, "bcd")
+}
+
+/// Reads a packed decimal field as an integer.
+pub(crate) fn read_packed<R: Read + ?Sized>(reader: &mut R, len: usize) -> Result<i128> {
+    check_len(len, 1, MAX_INT_BYTES)?;
+    let (negative, digits) = packed_digits(&read_field(reader, len, "packed decimal")?)?;
+    let value = to_int(&digits) as i128;
+    Ok(if negative { -value } else { value })
+}
+
+/// Reads a packed decimal field of any length as a string.
+pub(crate) fn read_packed_str<R: Read + ?Sized>(reader: &mut R, len: usize) -> Result<String> {
+    check_len(len, 1, usize::MAX)?;
+    let (negative, digits) = packed_digits(&read_field(reader, len, "packed decimal")?)?;
+    // negative zero is written as zero
+    let negative = negative && digits.iter().any(|digit| *digit != 0);
+    Ok(to_str(negative, &digits))
+}
+
+/// Writes an integer as a packed decimal field with the sign nibble `C` or `D`.
+pub(crate) fn write_packed<W: Write + ?Sized>(
+    writer: &mut W,
+    len: usize,
+    value: i128,
+) -> Result<()> {
+    check_len(len, 1, MAX_INT_BYTES)?;
+    let mut nibbles = to_nibbles(value.unsigned_abs(), len, 1).ok_or(Error::OutOfRange {
+        offset: None,
+        value,
+        type_name: "packed decimal",
+    })?;
+    nibbles[len * 2 - 1] = if value < 0 { 0xd } else { 0xc };
+    write_field(writer, &nibbles, "packed decimal")
+}
@@ -0,0 +1,228 @@
+use crate::{Error, Result, limit::read_bytes};
+use std::fmt::Write as _;
+use std::io::{Read, Seek, SeekFrom::Start as SeekPos, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Canonical,
+    Xxd,
+}
+
+/// Hexdump formatter in the style of `hexdump -C` or `xxd`.
+///
+/// Use `dump` for any `Read`, `ReadValAt::hexdump_at` for a range of a seekable stream
+/// and `dump_spans` to print parsed fields next to their bytes.
+#[derive(Debug, Clone)]
+pub struct HexDump {
+    style: Style,
+    width: usize,
+    group: usize,
+    uppercase: bool,
+}
+
+/// Labeled byte range for `HexDump::dump_spans`, e.g. a field a parser read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub offset: u64,
+    pub len: u64,
+    pub label: String,
+    pub value: Option<String>,
+}
+
+impl Span {
+    pub fn new(offset: u64, len: u64, label: impl Into<String>) -> Self {
+        Self {
+            offset,
+            len,
+            label: label.into(),
+            value: None,
+        }
+    }
+
+    /// Sets the decoded value that is printed after the label.
+    pub fn with_value(mut self, value: impl std::fmt::Display) -> Self {
+        self.value = Some(value.to_string());
+        self
+    }
+}
+
+impl Default for HexDump {
+    fn default() -> Self {
+        Self::canonical()
+    }
+}
+
+impl HexDump {
+    /// Creates a formatter like `hexdump -C` with 16 bytes per line in groups of 8.
+    pub fn canonical() -> Self {
+        Self {
+            style: Style::Canonical,
+            width: 16,
+            group: 8,
+            uppercase: false,
+        }
+    }
+
+    /// Creates a formatter like `xxd` with 16 bytes per line in groups of 2.
+    pub fn xxd() -> Self {
+        Self {
+            style: Style::Xxd,
+            width: 16,
+            group: 2,
+            uppercase: false,
+        }
+    }
+
+    /// Sets the number of bytes per line.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// Sets the number of bytes per group.
+    pub fn with_group(mut self, group: usize) -> Self {
+        self.group = group.max(1);
+        self
+    }
+
+    pub fn with_uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Formats one line, `pad` fills the ASCII column so annotations line up.
+    fn line(&self, offset: u64, bytes: &[u8], pad: bool) -> String {
+        let mut line = match self.style {
+            Style::Canonical => format!("{offset:08x}  "),
+            Style::Xxd => format!("{offset:08x}: "),
+        };
+        let byte_sep = match self.style {
+            Style::Canonical => " ",
+            Style::Xxd => "",
+        };
+
+        for i in 0..self.width {
+            match bytes.get(i) {
+                Some(byte) if self.uppercase => write!(line, "{byte:02X}{byte_sep}").unwrap(),
+                Some(byte) => write!(line, "{byte:02x}{byte_sep}").unwrap(),
+                None => write!(line, "  {byte_sep}").unwrap(),
+            }
+            if (i + 1) % self.group == 0 || i + 1 == self.width {
+                line.push(' ');
+            }
+        }
+
+        let ascii: String = bytes
+            .iter()
+            .map(|byte| match byte {
+                0x20..=0x7e => *byte as char,
+                _ => '.',
+            })
+            .chain(std::iter::repeat_n(
+                ' ',
+                if pad { self.width - bytes.len() } else { 0 },
+            ))
+            .collect();
+        match self.style {
+            Style::Canonical => write!(line, "|{ascii}|").unwrap(),
+            Style::Xxd => write!(line, " {ascii}").unwrap(),
+        }
+        line
+    }
+
+    /// Dumps the bytes of the reader with offsets starting at `start` and returns the number of bytes.
+    pub(crate) fn dump_from<R: Read + ?Sized>(
+        &self,
+        reader: &mut R,
+        start: u64,
+        target: &mut dyn Write,
+    ) -> Result<u64> {
+        let mut offset = start;
+        loop {
+            let mut bytes = Vec::with_capacity(self.width);
+            reader.take(self.width as u64).read_to_end(&mut bytes)?;
+            if bytes.is_empty() {
+                break;
+            }
+            writeln!(target, "{}", self.line(offset, &bytes, false))?;
+            offset += bytes.len() as u64;
+        }
+        if self.style == Style::Canonical && offset > start {
+            writeln!(target, "{offset:08x}")?;
+        }
+        Ok(offset - start)
+    }
+
+    /// Dumps everything the reader returns and returns the number of bytes.
+    pub fn dump<R: Read + ?Sized>(&self, reader: &mut R, target: &mut dyn Write) -> Result<u64> {
+        self.dump_from(reader, 0, target)
+    }
+
+    /// Formats bytes as a hexdump.
+    pub fn to_string(&self, bytes: &[u8]) -> String {
+        let mut text = Vec::new();
+        self.dump(&mut &bytes[..], &mut text).unwrap();
+        String::from_utf8(text).unwrap()
+    }
+
+    /// Dumps the bytes of each span with its label and value next to them, without moving the cursor.
+    ///
+    /// Spans are sorted by offset and start on a new line, the bytes between spans are dumped without a label.
+    /// Overlapping bytes are dumped once for each span.
+    pub fn dump_spans<R: Read + Seek + ?Sized>(
+        &self,
+        reader: &mut R,
+        spans: &[Span],
+        target: &mut dyn Write,
+    ) -> Result<()> {
+        let mut spans: Vec<&Span> = spans.iter().collect();
+        spans.sort_by_key(|span| span.offset);
+
+        let pos_before = reader.stream_position()?;
+        let result = spans.iter().enumerate().try_for_each(|(i, span)| {
+            self.dump_span(reader, span, target)?;
+            let end = span.offset + span.len;
+            match spans.get(i + 1) {
+                Some(next) if next.offset > end => {
+                    let gap = Span::new(end, next.offset - end, "");
+                    self.dump_span(reader, &gap, target)
+                }
+                _ => Ok(()),
+            }
+        });
+        reader.seek(SeekPos(pos_before))?;
+        result
+    }
+
+    fn dump_span<R: Read + Seek + ?Sized>(
+        &self,
+        reader: &mut R,
+        span: &Span,
+        target: &mut dyn Write,
+    ) -> Result<()> {
+        reader.seek(SeekPos(span.offset))?;
+        let bytes = read_bytes(reader, span.len as usize)
+            .map_err(|e| Error::for_value(e, Some(span.offset), "span", span.len as usize))?;
+
+        let mut annotation = span.label.clone();
+        if let Some(value) = &span.value {
+            write!(annotation, ": {value}").unwrap();
+        }
+        let mut lines = bytes.chunks(self.width).peekable();
+        if lines.peek().is_none() {
+            writeln!(
+                target,
+                "{}  {annotation}",
+                self.line(span.offset, &[], true)
+            )?;
+        }
+        for (i, chunk) in lines.enumerate() {
+            let offset = span.offset + (i * self.width) as u64;
+            match i == 0 && !annotation.is_empty() {
+                true => writeln!(target, "{}  {annotation}", self.line(offset, chunk, true))?,
+                false => writeln!(target, "{}", self.line(offset, chunk, false))?,
+            }
+        }
+        Ok(())
+    }
+}
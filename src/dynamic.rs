@@ -1,5 +1,4 @@
-use crate::error::Result;
-use std::io::{Error, ErrorKind::InvalidData};
+use crate::{Error, Result};
 
 // marker trait
 pub trait Dynamic: Sized {
@@ -19,7 +18,10 @@ impl Dynamic for Vec<u8> {
 
 impl Dynamic for String {
     fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
-        String::from_utf8(bytes).map_err(|_| Error::new(InvalidData, "Invalid UTF-8"))
+        String::from_utf8(bytes).map_err(|e| Error::InvalidUtf8 {
+            offset: None,
+            len: e.as_bytes().len(),
+        })
     }
     fn into_bytes(&self) -> Result<&[u8]> {
         Ok(self.as_bytes())
@@ -0,0 +1,294 @@
+//! Hex, Base64 and Base32 encoding as `Read` and `Write` adapters.
+//!
+//! `Encoder` turns everything written to it into text, `Decoder` reads binary data out of text,
+//! so `ReadVal` and `WriteVal` work directly on encoded streams:
+//!
+//! ```
+//! use dh::{ReadVal, encoding::{Decoder, Encoding}};
+//! use std::io::Cursor;
+//!
+//! let mut reader = Decoder::new(Cursor::new("KgAAAA=="), Encoding::BASE64);
+//! assert_eq!(reader.read_u32_le().unwrap(), 42);
+//! ```
+
+use crate::{Error, Result};
+use std::io::{self, Read, Write};
+
+const INVALID: u8 = 0xff;
+const PAD: u8 = b'=';
+
+/// Alphabet and conventions of a hex, Base64 or Base32 encoding.
+///
+/// Decoding skips ASCII whitespace and accepts padding even if the encoding doesn't write it.
+/// Hex and Base32 are decoded case-insensitively.
+#[derive(Clone, Copy)]
+pub struct Encoding {
+    name: &'static str,
+    alphabet: &'static [u8],
+    bits: u32,
+    padding: bool,
+    table: [u8; 256],
+}
+
+impl Encoding {
+    /// Lowercase hex (base16).
+    pub const HEX_LOWER: Self = Self::new("hex", b"0123456789abcdef", false);
+    /// Uppercase hex (base16).
+    pub const HEX_UPPER: Self = Self::new("hex", b"0123456789ABCDEF", false);
+    /// Standard Base64 with padding (RFC 4648).
+    pub const BASE64: Self = Self::new(
+        "base64",
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+        true,
+    );
+    /// URL and filename safe Base64 with padding (RFC 4648).
+    pub const BASE64_URL: Self = Self::new(
+        "base64",
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+        true,
+    );
+    /// Standard Base32 with padding (RFC 4648).
+    pub const BASE32: Self = Self::new("base32", b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567", true);
+
+    const fn new(name: &'static str, alphabet: &'static [u8], padding: bool) -> Self {
+        let mut table = [INVALID; 256];
+        let mut i = 0;
+        while i < alphabet.len() {
+            table[alphabet[i] as usize] = i as u8;
+            i += 1;
+        }
+
+        // accept the other case for letters that are not part of the alphabet in that case
+        let mut i = 0;
+        while i < alphabet.len() {
+            let other = alphabet[i] ^ 0x20;
+            if alphabet[i].is_ascii_alphabetic() && table[other as usize] == INVALID {
+                table[other as usize] = i as u8;
+            }
+            i += 1;
+        }
+
+        Self {
+            name,
+            alphabet,
+            bits: alphabet.len().trailing_zeros(),
+            padding,
+            table,
+        }
+    }
+
+    /// Returns the encoding with or without padding when encoding.
+    pub const fn with_padding(mut self, padding: bool) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns the number of characters that encode a whole number of bytes, e.g. 4 for Base64.
+    const fn group(self) -> u32 {
+        match self.bits {
+            4 => 2,
+            5 => 8,
+            _ => 4,
+        }
+    }
+
+    pub fn encode(self, bytes: &[u8]) -> String {
+        let mut encoder = Encoder::new(Vec::new(), self);
+        encoder.encode(bytes);
+        encoder.encode_last();
+        String::from_utf8(encoder.out).unwrap()
+    }
+
+    pub fn decode(self, text: impl AsRef<[u8]>) -> Result<Vec<u8>> {
+        let mut decoder = Decoder::new(io::empty(), self);
+        decoder.decode(text.as_ref())?;
+        decoder.decode_last()?;
+        Ok(decoder.out)
+    }
+}
+
+impl std::fmt::Debug for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Encoding")
+            .field("name", &self.name)
+            .field("alphabet", &String::from_utf8_lossy(self.alphabet))
+            .field("padding", &self.padding)
+            .finish()
+    }
+}
+
+impl PartialEq for Encoding {
+    fn eq(&self, other: &Self) -> bool {
+        self.alphabet == other.alphabet && self.padding == other.padding
+    }
+}
+
+impl Eq for Encoding {}
+
+/// Wrapper for `Write` that encodes everything written to it as text.
+///
+/// Call `finish` to write the last partial group and the padding.
+pub struct Encoder<W: Write> {
+    inner: W,
+    encoding: Encoding,
+    acc: u32,
+    count: u32,
+    chars: u32,
+    out: Vec<u8>,
+}
+
+impl<W: Write> Encoder<W> {
+    pub fn new(inner: W, encoding: Encoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            acc: 0,
+            count: 0,
+            chars: 0,
+            out: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    fn put(&mut self, value: u32) {
+        self.out
+            .push(self.encoding.alphabet[(value & ((1 << self.encoding.bits) - 1)) as usize]);
+        self.chars = (self.chars + 1) % self.encoding.group();
+    }
+
+    fn encode(&mut self, bytes: &[u8]) {
+        let bits = self.encoding.bits;
+        for byte in bytes {
+            self.acc = self.acc << 8 | *byte as u32;
+            self.count += 8;
+            while self.count >= bits {
+                self.count -= bits;
+                self.put(self.acc >> self.count);
+            }
+            self.acc &= (1 << self.count) - 1;
+        }
+    }
+
+    fn encode_last(&mut self) {
+        if self.count > 0 {
+            self.put(self.acc << (self.encoding.bits - self.count));
+            self.count = 0;
+        }
+        while self.encoding.padding && self.chars != 0 {
+            self.out.push(PAD);
+            self.chars = (self.chars + 1) % self.encoding.group();
+        }
+    }
+
+    /// Writes the last partial group and the padding and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.encode_last();
+        self.inner.write_all(&self.out)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encode(buf);
+        self.inner.write_all(&self.out)?;
+        self.out.clear();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Wrapper for `Read` that decodes text and returns the binary data.
+pub struct Decoder<R: Read> {
+    inner: R,
+    encoding: Encoding,
+    acc: u32,
+    count: u32,
+    padded: bool,
+    done: bool,
+    out: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(inner: R, encoding: Encoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            acc: 0,
+            count: 0,
+            padded: false,
+            done: false,
+            out: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn invalid(&self, reason: &'static str) -> Error {
+        Error::InvalidEncoding {
+            offset: None,
+            type_name: self.encoding.name,
+            reason,
+        }
+    }
+
+    fn decode(&mut self, text: &[u8]) -> Result<()> {
+        let bits = self.encoding.bits;
+        for byte in text {
+            match (*byte, self.encoding.table[*byte as usize]) {
+                (byte, _) if byte.is_ascii_whitespace() => {}
+                (PAD, _) => self.padded = true,
+                (_, INVALID) => return Err(self.invalid("invalid character")),
+                _ if self.padded => return Err(self.invalid("data after padding")),
+                (_, value) => {
+                    self.acc = self.acc << bits | value as u32;
+                    self.count += bits;
+                    if self.count >= 8 {
+                        self.count -= 8;
+                        self.out.push((self.acc >> self.count) as u8);
+                        self.acc &= (1 << self.count) - 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the text didn't end in the middle of a byte.
+    fn decode_last(&mut self) -> Result<()> {
+        self.done = true;
+        match self.count >= self.encoding.bits {
+            true => Err(self.invalid("truncated input")),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut text = [0; 4096];
+        while self.pos == self.out.len() && !self.done && !buf.is_empty() {
+            self.out.clear();
+            self.pos = 0;
+            match self.inner.read(&mut text)? {
+                0 => self.decode_last()?,
+                len => self.decode(&text[..len])?,
+            }
+        }
+
+        let len = buf.len().min(self.out.len() - self.pos);
+        buf[..len].copy_from_slice(&self.out[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
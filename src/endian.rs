@@ -0,0 +1,216 @@
+use crate::{Endianess, Primitive, ReadVal, ReadValAt, Result, WriteVal, WriteValAt};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+macro_rules! read_default {
+    ($fn_name:ident, $return_type:ty) => {
+        /// Typed wrapper around `read_val`.
+        pub fn $fn_name(&mut self) -> Result<$return_type> {
+            self.read_val()
+        }
+    };
+}
+
+macro_rules! read_default_at {
+    ($fn_name:ident, $return_type:ty) => {
+        /// Typed wrapper around `read_val_at`.
+        pub fn $fn_name(&mut self, pos: usize) -> Result<$return_type> {
+            self.read_val_at(pos)
+        }
+    };
+}
+
+macro_rules! write_default {
+    ($fn_name:ident, $data_type:ty) => {
+        /// Typed wrapper around `write_val`.
+        pub fn $fn_name(&mut self, data: $data_type) -> Result<()> {
+            self.write_val(data)
+        }
+    };
+}
+
+macro_rules! write_default_at {
+    ($fn_name:ident, $data_type:ty) => {
+        /// Typed wrapper around `write_val_at`.
+        pub fn $fn_name(&mut self, pos: usize, data: $data_type) -> Result<()> {
+            self.write_val_at(pos, data)
+        }
+    };
+}
+
+/// Wrapper that reads and writes values in a default byte order.
+///
+/// The typed methods like `read_u32` or `write_u64` don't take an `Endianess` here,
+/// the explicit methods like `read_u32_be` are still available through `ReadVal` and `WriteVal`.
+/// The byte order can be changed at any time, e.g. after detecting it from a file header.
+pub struct Endian<T> {
+    inner: T,
+    endianess: Endianess,
+}
+
+impl<T> Endian<T> {
+    pub fn new(inner: T, endianess: Endianess) -> Self {
+        Self { inner, endianess }
+    }
+
+    pub fn endianess(&self) -> Endianess {
+        self.endianess
+    }
+
+    pub fn set_endianess(&mut self, endianess: Endianess) {
+        self.endianess = endianess;
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read> Endian<T> {
+    /// Reads a primitive value using the default byte order.
+    pub fn read_val<V: Primitive<U, S>, U, const S: usize>(&mut self) -> Result<V> {
+        use Endianess::*;
+        match self.endianess {
+            Little => self.inner.read_le(),
+            Big => self.inner.read_be(),
+            Native => self.inner.read_ne(),
+        }
+    }
+
+    read_default!(read_u16, u16);
+    read_default!(read_u32, u32);
+    read_default!(read_u64, u64);
+    read_default!(read_u128, u128);
+    read_default!(read_usize, usize);
+
+    read_default!(read_i16, i16);
+    read_default!(read_i32, i32);
+    read_default!(read_i64, i64);
+    read_default!(read_i128, i128);
+    read_default!(read_isize, isize);
+
+    read_default!(read_f32, f32);
+    read_default!(read_f64, f64);
+
+    read_default!(read_char, char);
+}
+
+impl<T: Read + Seek> Endian<T> {
+    /// Reads a primitive value at a specific position using the default byte order.
+    pub fn read_val_at<V: Primitive<U, S>, U, const S: usize>(&mut self, pos: usize) -> Result<V> {
+        use Endianess::*;
+        match self.endianess {
+            Little => self.inner.read_le_at(pos),
+            Big => self.inner.read_be_at(pos),
+            Native => self.inner.read_ne_at(pos),
+        }
+    }
+
+    read_default_at!(read_u16_at, u16);
+    read_default_at!(read_u32_at, u32);
+    read_default_at!(read_u64_at, u64);
+    read_default_at!(read_u128_at, u128);
+    read_default_at!(read_usize_at, usize);
+
+    read_default_at!(read_i16_at, i16);
+    read_default_at!(read_i32_at, i32);
+    read_default_at!(read_i64_at, i64);
+    read_default_at!(read_i128_at, i128);
+    read_default_at!(read_isize_at, isize);
+
+    read_default_at!(read_f32_at, f32);
+    read_default_at!(read_f64_at, f64);
+
+    read_default_at!(read_char_at, char);
+}
+
+impl<T: Write> Endian<T> {
+    /// Writes a primitive value using the default byte order.
+    pub fn write_val<V: Primitive<U, S>, U, const S: usize>(&mut self, data: V) -> Result<()> {
+        use Endianess::*;
+        match self.endianess {
+            Little => self.inner.write_le(data),
+            Big => self.inner.write_be(data),
+            Native => self.inner.write_ne(data),
+        }
+    }
+
+    write_default!(write_u16, u16);
+    write_default!(write_u32, u32);
+    write_default!(write_u64, u64);
+    write_default!(write_u128, u128);
+    write_default!(write_usize, usize);
+
+    write_default!(write_i16, i16);
+    write_default!(write_i32, i32);
+    write_default!(write_i64, i64);
+    write_default!(write_i128, i128);
+    write_default!(write_isize, isize);
+
+    write_default!(write_f32, f32);
+    write_default!(write_f64, f64);
+
+    write_default!(write_char, char);
+}
+
+impl<T: Write + Seek> Endian<T> {
+    /// Writes a primitive value at a specific position using the default byte order.
+    pub fn write_val_at<V: Primitive<U, S>, U, const S: usize>(
+        &mut self,
+        pos: usize,
+        data: V,
+    ) -> Result<()> {
+        use Endianess::*;
+        match self.endianess {
+            Little => self.inner.write_le_at(pos, data),
+            Big => self.inner.write_be_at(pos, data),
+            Native => self.inner.write_ne_at(pos, data),
+        }
+    }
+
+    write_default_at!(write_u16_at, u16);
+    write_default_at!(write_u32_at, u32);
+    write_default_at!(write_u64_at, u64);
+    write_default_at!(write_u128_at, u128);
+    write_default_at!(write_usize_at, usize);
+
+    write_default_at!(write_i16_at, i16);
+    write_default_at!(write_i32_at, i32);
+    write_default_at!(write_i64_at, i64);
+    write_default_at!(write_i128_at, i128);
+    write_default_at!(write_isize_at, isize);
+
+    write_default_at!(write_f32_at, f32);
+    write_default_at!(write_f64_at, f64);
+
+    write_default_at!(write_char_at, char);
+}
+
+impl<T: Read> Read for Endian<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<T: Write> Write for Endian<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: Seek> Seek for Endian<T> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -1,2 +1,337 @@
-pub type Error = std::io::Error;
+use std::fmt;
+use std::io::{self, ErrorKind};
+
+/// Errors returned by all reading and writing methods of `dh`.
+///
+/// Every variant records the stream offset if it is known (e.g. for the `*_at` methods, `CountingReader`
+/// or `ReadValAt::read_positioned`),
+/// labels added with `ResultExt::context` wrap the error in `Error::Context`.
+///
+/// Converts from and to `std::io::Error`, so it can be used with `?` in both directions.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The underlying stream returned an error.
+    Io {
+        source: io::Error,
+        offset: Option<u64>,
+    },
+    /// The stream ended before the requested value could be read completely.
+    UnexpectedEof {
+        offset: Option<u64>,
+        type_name: &'static str,
+        size: usize,
+    },
+    /// A byte buffer had the wrong length to be cast to a value.
+    SizeMismatch {
+        offset: Option<u64>,
+        type_name: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// A byte buffer was not aligned properly to be borrowed as a value.
+    Misaligned {
+        offset: Option<u64>,
+        type_name: &'static str,
+        align: usize,
+    },
+    /// A value doesn't fit into the integer type it should be written as.
+    OutOfRange {
+        offset: Option<u64>,
+        value: i128,
+        type_name: &'static str,
+    },
+    /// A floating point value can't be represented in the format it should be written as.
+    Unrepresentable {
+        offset: Option<u64>,
+        value: f64,
+        type_name: &'static str,
+    },
+    /// The bytes read are not a valid encoding of the value.
+    InvalidEncoding {
+        offset: Option<u64>,
+        type_name: &'static str,
+        reason: &'static str,
+    },
+    /// An integer doesn't match any variant of the enum it should be converted to.
+    UnknownDiscriminant {
+        offset: Option<u64>,
+        value: i128,
+        type_name: &'static str,
+    },
+    /// A flags value has bits set that are not defined.
+    UnknownFlags {
+        offset: Option<u64>,
+        bits: u128,
+        type_name: &'static str,
+    },
+    /// A stored checksum doesn't match the one computed over the data.
+    ChecksumMismatch {
+        offset: Option<u64>,
+        type_name: &'static str,
+        expected: u64,
+        actual: u64,
+    },
+    /// A string value was not valid UTF-8.
+    InvalidUtf8 { offset: Option<u64>, len: usize },
+    /// A read was larger than the configured limit.
+    LimitExceeded {
+        offset: Option<u64>,
+        requested: u64,
+        limit: u64,
+    },
+    /// A user-supplied label describing what was being read or written.
+    Context { label: String, source: Box<Error> },
+}
+
+impl Error {
+    /// Creates an error from a failed read or write of a value with the given type and size.
+    pub(crate) fn for_value(
+        error: impl Into<Error>,
+        offset: Option<u64>,
+        type_name: &'static str,
+        size: usize,
+    ) -> Self {
+        match error.into() {
+            Error::Io {
+                source,
+                offset: known,
+            } if source.kind() == ErrorKind::UnexpectedEof => Error::UnexpectedEof {
+                offset: offset.or(known),
+                type_name,
+                size,
+            },
+            error => error.with_offset(offset),
+        }
+    }
+
+    /// Sets the offset of the error, if it's not known yet.
+    pub fn with_offset(mut self, offset: Option<u64>) -> Self {
+        let mut error = &mut self;
+        while let Error::Context { source, .. } = error {
+            error = source.as_mut();
+        }
+
+        let known = match error {
+            Error::Io { offset, .. }
+            | Error::UnexpectedEof { offset, .. }
+            | Error::SizeMismatch { offset, .. }
+            | Error::Misaligned { offset, .. }
+            | Error::OutOfRange { offset, .. }
+            | Error::Unrepresentable { offset, .. }
+            | Error::InvalidEncoding { offset, .. }
+            | Error::UnknownDiscriminant { offset, .. }
+            | Error::UnknownFlags { offset, .. }
+            | Error::ChecksumMismatch { offset, .. }
+            | Error::InvalidUtf8 { offset, .. }
+            | Error::LimitExceeded { offset, .. } => offset,
+            Error::Context { .. } => unreachable!(),
+        };
+        if known.is_none() {
+            *known = offset;
+        }
+        self
+    }
+
+    /// Wraps the error in a context label.
+    pub fn context(self, label: impl Into<String>) -> Self {
+        Error::Context {
+            label: label.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// Returns the stream offset where the error occurred, if it's known.
+    pub fn offset(&self) -> Option<u64> {
+        match self.root() {
+            Error::Io { offset, .. }
+            | Error::UnexpectedEof { offset, .. }
+            | Error::SizeMismatch { offset, .. }
+            | Error::Misaligned { offset, .. }
+            | Error::OutOfRange { offset, .. }
+            | Error::Unrepresentable { offset, .. }
+            | Error::InvalidEncoding { offset, .. }
+            | Error::UnknownDiscriminant { offset, .. }
+            | Error::UnknownFlags { offset, .. }
+            | Error::ChecksumMismatch { offset, .. }
+            | Error::InvalidUtf8 { offset, .. }
+            | Error::LimitExceeded { offset, .. } => *offset,
+            Error::Context { .. } => unreachable!(),
+        }
+    }
+
+    /// Returns the context labels, outermost first.
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels = Vec::new();
+        let mut error = self;
+        while let Error::Context { label, source } = error {
+            labels.push(label.as_str());
+            error = source;
+        }
+        labels
+    }
+
+    /// Returns the context labels joined to a path like `header.entries[3].name`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for label in self.labels() {
+            if !path.is_empty() && !label.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(label);
+        }
+        path
+    }
+
+    /// Returns the innermost error without context labels.
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::Context { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Returns the corresponding `std::io::ErrorKind`.
+    pub fn kind(&self) -> ErrorKind {
+        match self.root() {
+            Error::Io { source, .. } => source.kind(),
+            Error::UnexpectedEof { .. } => ErrorKind::UnexpectedEof,
+            Error::SizeMismatch { .. }
+            | Error::Misaligned { .. }
+            | Error::InvalidEncoding { .. }
+            | Error::UnknownDiscriminant { .. }
+            | Error::UnknownFlags { .. }
+            | Error::ChecksumMismatch { .. }
+            | Error::InvalidUtf8 { .. } => ErrorKind::InvalidData,
+            Error::OutOfRange { .. } | Error::Unrepresentable { .. } => ErrorKind::InvalidInput,
+            Error::LimitExceeded { .. } => ErrorKind::QuotaExceeded,
+            Error::Context { .. } => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Error::Context { .. } = self {
+            return write!(f, "{}: {}", self.path(), self.root());
+        }
+
+        match self {
+            Error::Io { source, .. } => write!(f, "{source}")?,
+            Error::UnexpectedEof {
+                type_name, size, ..
+            } => write!(
+                f,
+                "Unexpected end of stream while reading {type_name} ({size} bytes)"
+            )?,
+            Error::SizeMismatch {
+                type_name,
+                expected,
+                actual,
+                ..
+            } => write!(
+                f,
+                "Cannot cast {actual} bytes to {type_name} ({expected} bytes)"
+            )?,
+            Error::Misaligned {
+                type_name, align, ..
+            } => write!(
+                f,
+                "Cannot borrow bytes as {type_name}, they are not aligned to {align} bytes"
+            )?,
+            Error::OutOfRange {
+                value, type_name, ..
+            } => write!(f, "Value {value} is out of range for {type_name}")?,
+            Error::Unrepresentable {
+                value, type_name, ..
+            } => write!(f, "Value {value} cannot be represented as {type_name}")?,
+            Error::InvalidEncoding {
+                type_name, reason, ..
+            } => write!(f, "Invalid {type_name}: {reason}")?,
+            Error::UnknownDiscriminant {
+                value, type_name, ..
+            } => write!(f, "Unknown discriminant {value} for {type_name}")?,
+            Error::UnknownFlags {
+                bits, type_name, ..
+            } => write!(f, "Unknown bits {bits:#x} in {type_name}")?,
+            Error::ChecksumMismatch {
+                type_name,
+                expected,
+                actual,
+                ..
+            } => write!(
+                f,
+                "{type_name} mismatch, stored {expected:#x} but computed {actual:#x}"
+            )?,
+            Error::InvalidUtf8 { len, .. } => write!(f, "Invalid UTF-8 in {len} bytes")?,
+            Error::LimitExceeded {
+                requested, limit, ..
+            } => write!(
+                f,
+                "Read of {requested} bytes exceeds the limit of {limit} bytes"
+            )?,
+            Error::Context { .. } => unreachable!(),
+        }
+
+        match self.offset() {
+            Some(offset) => write!(f, " at offset {offset:#x}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        if error.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            return *error.into_inner().unwrap().downcast::<Error>().unwrap();
+        }
+
+        Error::Io {
+            source: error,
+            offset: None,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io {
+                source,
+                offset: None,
+            } => source,
+            error => io::Error::new(error.kind(), error),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Extension trait for `Result` that adds context labels to errors.
+pub trait ResultExt<T> {
+    /// Wraps the error in a context label.
+    fn context(self, label: impl Into<String>) -> Result<T>;
+
+    /// Wraps the error in a lazily created context label.
+    fn with_context<L: Into<String>>(self, label: impl FnOnce() -> L) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> ResultExt<T> for std::result::Result<T, E> {
+    fn context(self, label: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.into().context(label))
+    }
+
+    fn with_context<L: Into<String>>(self, label: impl FnOnce() -> L) -> Result<T> {
+        self.map_err(|e| e.into().context(label()))
+    }
+}
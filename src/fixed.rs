@@ -0,0 +1,163 @@
+use crate::{Error, Primitive, Result};
+use std::any::type_name;
+use std::fmt;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Integer types that can store the bits of a `Fixed` value.
+pub trait FixedBits: sealed::Sealed + Copy + Default + 'static {
+    const BITS: u32;
+    const MIN: i128;
+    const MAX: i128;
+
+    fn to_i128(self) -> i128;
+
+    /// Converts the value, the caller checks that it's in range.
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! fixed_bits {
+    ($($type:ty),*) => {
+        $(
+            impl sealed::Sealed for $type {}
+
+            impl FixedBits for $type {
+                const BITS: u32 = <$type>::BITS;
+                const MIN: i128 = <$type>::MIN as i128;
+                const MAX: i128 = <$type>::MAX as i128;
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value as $type
+                }
+            }
+        )*
+    };
+}
+
+fixed_bits!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Fixed-point number stored as the integer `T` with `FRAC` fractional bits.
+///
+/// The value is `bits / 2^FRAC`, reading and writing keeps the bits as they are,
+/// so round-trips are always lossless.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Fixed<T: FixedBits, const FRAC: u32> {
+    bits: T,
+}
+
+/// Signed 16.16 fixed-point number, e.g. the TrueType `Fixed` type.
+pub type Fixed16_16 = Fixed<i32, 16>;
+
+/// Unsigned 16.16 fixed-point number, e.g. QuickTime sample rates.
+pub type UFixed16_16 = Fixed<u32, 16>;
+
+/// Signed 8.8 fixed-point number, e.g. QuickTime volumes.
+pub type Fixed8_8 = Fixed<i16, 8>;
+
+/// Signed 2.14 fixed-point number used by TrueType.
+pub type F2Dot14 = Fixed<i16, 14>;
+
+/// Signed 2.30 fixed-point number used in QuickTime matrices.
+pub type Fixed2_30 = Fixed<i32, 30>;
+
+/// Signed Q15 fixed-point number with a range of `[-1, 1)`.
+pub type Q15 = Fixed<i16, 15>;
+
+/// Signed Q31 fixed-point number with a range of `[-1, 1)`.
+pub type Q31 = Fixed<i32, 31>;
+
+impl<T: FixedBits, const FRAC: u32> Fixed<T, FRAC> {
+    const CHECK: () = assert!(FRAC <= T::BITS, "too many fractional bits");
+
+    pub const fn from_bits(bits: T) -> Self {
+        Self { bits }
+    }
+
+    pub const fn to_bits(self) -> T {
+        self.bits
+    }
+
+    /// Converts the value to `f64`, which is exact unless `T` has more than 53 significant bits.
+    pub fn to_f64(self) -> f64 {
+        let () = Self::CHECK;
+        self.bits.to_i128() as f64 / (1u128 << FRAC) as f64
+    }
+
+    /// Converts an `f64` to the nearest fixed-point value, ties to even.
+    ///
+    /// Fails for NaN and values outside the range of the fixed-point type.
+    pub fn from_f64(value: f64) -> Result<Self> {
+        let () = Self::CHECK;
+        let scaled = (value * (1u128 << FRAC) as f64).round_ties_even();
+        if !(T::MIN as f64..=T::MAX as f64).contains(&scaled) || scaled as i128 > T::MAX {
+            return Err(Error::Unrepresentable {
+                offset: None,
+                value,
+                type_name: type_name::<Self>(),
+            });
+        }
+        Ok(Self::from_bits(T::from_i128(scaled as i128)))
+    }
+}
+
+impl<T: FixedBits, const FRAC: u32> From<Fixed<T, FRAC>> for f64 {
+    fn from(value: Fixed<T, FRAC>) -> Self {
+        value.to_f64()
+    }
+}
+
+impl<T: FixedBits, const FRAC: u32> TryFrom<f64> for Fixed<T, FRAC> {
+    type Error = Error;
+
+    fn try_from(value: f64) -> Result<Self> {
+        Self::from_f64(value)
+    }
+}
+
+impl<T: FixedBits, const FRAC: u32> fmt::Debug for Fixed<T, FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Fixed").field(&self.to_f64()).finish()
+    }
+}
+
+impl<T: FixedBits, const FRAC: u32> fmt::Display for Fixed<T, FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl<T, const FRAC: u32, const S: usize> Primitive<Fixed<T, FRAC>, S> for Fixed<T, FRAC>
+where
+    T: FixedBits + Primitive<T, S>,
+{
+    fn from_ne_bytes(bytes: [u8; S]) -> Result<Self> {
+        T::from_ne_bytes(bytes).map(Self::from_bits)
+    }
+
+    fn from_le_bytes(bytes: [u8; S]) -> Result<Self> {
+        T::from_le_bytes(bytes).map(Self::from_bits)
+    }
+
+    fn from_be_bytes(bytes: [u8; S]) -> Result<Self> {
+        T::from_be_bytes(bytes).map(Self::from_bits)
+    }
+
+    fn to_ne_bytes(self) -> [u8; S] {
+        self.bits.to_ne_bytes()
+    }
+
+    fn to_le_bytes(self) -> [u8; S] {
+        self.bits.to_le_bytes()
+    }
+
+    fn to_be_bytes(self) -> [u8; S] {
+        self.bits.to_be_bytes()
+    }
+}
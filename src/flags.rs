@@ -0,0 +1,39 @@
+use crate::{Error, Result};
+use std::any::type_name;
+
+/// Set of bit flags that can be read with `read_flags`.
+///
+/// The trait mirrors the `bitflags` crate, so it only takes a few lines to implement it
+/// for a generated flags type or a plain newtype around an integer.
+pub trait Flags: Sized {
+    /// Integer type that stores the flags.
+    type Bits: Copy + Into<u128>;
+
+    /// All defined flags combined.
+    const ALL: Self::Bits;
+
+    /// Creates the set from raw bits, keeping bits that are not defined.
+    fn from_bits_retain(bits: Self::Bits) -> Self;
+}
+
+/// Creates the set from raw bits, failing if bits are set that are not defined.
+pub(crate) fn from_bits<F: Flags>(bits: F::Bits) -> Result<F> {
+    let unknown = bits.into() & !F::ALL.into();
+    if unknown != 0 {
+        return Err(Error::UnknownFlags {
+            offset: None,
+            bits: unknown,
+            type_name: type_name::<F>(),
+        });
+    }
+    Ok(F::from_bits_retain(bits))
+}
+
+/// Converts an integer to an enum, failing if it's not a known discriminant.
+pub(crate) fn to_enum<E: TryFrom<T>, T: Copy + Into<i128>>(value: T) -> Result<E> {
+    E::try_from(value).map_err(|_| Error::UnknownDiscriminant {
+        offset: None,
+        value: value.into(),
+        type_name: type_name::<E>(),
+    })
+}
@@ -0,0 +1,114 @@
+use crate::{Endianess, Error, Result, WriteVal, limit::read_bytes};
+use std::io::{Read, Write};
+
+mod half;
+mod ibm;
+mod vax;
+mod x87;
+
+pub use half::{
+    bf16_to_f32, bf16_to_f32_slice, f16_to_f32, f16_to_f32_slice, f32_to_bf16, f32_to_bf16_slice,
+    f32_to_f16, f32_to_f16_slice,
+};
+pub use ibm::{f64_to_ibm32, f64_to_ibm64, ibm32_to_f64, ibm64_to_f64};
+pub use vax::{f64_to_vax_d, f64_to_vax_f, f64_to_vax_g, vax_d_to_f64, vax_f_to_f64, vax_g_to_f64};
+pub use x87::{f64_to_x87, x87_to_f64};
+pub(crate) use x87::{f80_from_bytes, f80_to_bytes};
+
+/// Shifts `value` right by `shift` bits, rounding to nearest and ties to even.
+pub(crate) fn round_shift(value: u64, shift: u32) -> u64 {
+    if shift == 0 {
+        return value;
+    }
+    if shift > 64 {
+        return 0;
+    }
+
+    let quotient = value.checked_shr(shift).unwrap_or(0);
+    let rem = value & (u64::MAX >> (64 - shift));
+    let half = 1 << (shift - 1);
+    if rem > half || (rem == half && quotient & 1 == 1) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Builds the `f64` closest to `man * 2^exp`, rounding to nearest and ties to even.
+///
+/// Values too large become infinity, values too small become subnormals or zero.
+pub(crate) fn compose_f64(negative: bool, man: u64, exp: i64) -> f64 {
+    let sign = (negative as u64) << 63;
+    if man == 0 {
+        return f64::from_bits(sign);
+    }
+
+    // normalize, so the value is `man * 2^(exp - 63)` with the highest bit set
+    let zeros = man.leading_zeros();
+    let man = man << zeros;
+    let exp = exp - zeros as i64 + 63;
+
+    if exp > 1023 {
+        return f64::from_bits(sign | 0x7ff0_0000_0000_0000);
+    }
+    if exp < -1022 {
+        let shift = (11 - 1022 - exp).min(65) as u32;
+        return f64::from_bits(sign | round_shift(man, shift));
+    }
+
+    // rounding may carry into the exponent, which also yields infinity correctly
+    let bits = ((exp + 1022) as u64) << 52;
+    f64::from_bits(sign | (bits + round_shift(man, 11)))
+}
+
+/// Splits a finite, non-zero `f64` into its sign, a 53-bit mantissa with the highest bit set,
+/// and the binary exponent `exp` so that the value is in `[2^(exp - 1), 2^exp)`.
+pub(crate) fn decompose_f64(value: f64) -> (bool, u64, i64) {
+    let bits = value.to_bits();
+    let negative = bits >> 63 == 1;
+    let exp = ((bits >> 52) & 0x7ff) as i64;
+    let man = bits & 0xf_ffff_ffff_ffff;
+
+    match exp {
+        0 => {
+            let zeros = man.leading_zeros() as i64 - 11;
+            (negative, man << zeros, -1021 - zeros)
+        }
+        _ => (negative, man | 1 << 52, exp - 1022),
+    }
+}
+
+/// Reads `len` 16-bit values for the bulk float conversions.
+pub(crate) fn read_u16_vec<R: Read + ?Sized>(
+    reader: &mut R,
+    len: usize,
+    endianess: Endianess,
+) -> Result<Vec<u16>> {
+    let size = len.saturating_mul(2);
+    let bytes = read_bytes(reader, size).map_err(|e| Error::for_value(e, None, "[u16]", size))?;
+
+    let from_bytes = match endianess {
+        Endianess::Little => u16::from_le_bytes,
+        Endianess::Big => u16::from_be_bytes,
+        Endianess::Native => u16::from_ne_bytes,
+    };
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|chunk| from_bytes([chunk[0], chunk[1]]))
+        .collect())
+}
+
+/// Writes 16-bit values for the bulk float conversions.
+pub(crate) fn write_u16_slice<W: Write + ?Sized>(
+    mut writer: &mut W,
+    endianess: Endianess,
+    data: &[u16],
+) -> Result<()> {
+    let to_bytes = match endianess {
+        Endianess::Little => u16::to_le_bytes,
+        Endianess::Big => u16::to_be_bytes,
+        Endianess::Native => u16::to_ne_bytes,
+    };
+    let bytes: Vec<u8> = data.iter().flat_map(|value| to_bytes(*value)).collect();
+    writer.write_dynamic(bytes)
+}
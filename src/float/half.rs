@@ -0,0 +1,88 @@
+use super::round_shift;
+
+/// Converts an IEEE 754 half precision float to `f32`, which is always exact.
+///
+/// Subnormals, infinities and NaN payloads are preserved.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exp = ((bits >> 10) & 0x1f) as u32;
+    let man = (bits & 0x3ff) as u32;
+
+    match exp {
+        0 => {
+            // zero or subnormal, the value is `man * 2^-24`
+            let value = man as f32 / (1 << 24) as f32;
+            f32::from_bits(sign | value.to_bits())
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (man << 13)),
+        _ => f32::from_bits(sign | ((exp + 127 - 15) << 23) | (man << 13)),
+    }
+}
+
+/// Converts an `f32` to an IEEE 754 half precision float, rounding to nearest and ties to even.
+///
+/// Values too large for `f16` become infinity, values too small become subnormals or zero.
+/// NaN payloads are truncated to their upper bits and the result is always a quiet NaN.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+
+    if exp == 0xff {
+        return match man {
+            0 => sign | 0x7c00,
+            _ => sign | 0x7e00 | (man >> 13) as u16,
+        };
+    }
+
+    let exp = exp - 127 + 15;
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exp <= 0 {
+        // subnormal, the mantissa includes the implicit bit and is shifted into place
+        let man = (man | 0x80_0000) as u64;
+        return sign | round_shift(man, (14 - exp) as u32) as u16;
+    }
+
+    // rounding may carry into the exponent, which also yields infinity correctly
+    let half = ((exp as u32) << 10) | (man >> 13);
+    sign | round_shift(((half as u64) << 13) | (man & 0x1fff) as u64, 13) as u16
+}
+
+/// Converts a bfloat16 to `f32`, which is always exact.
+pub fn bf16_to_f32(bits: u16) -> f32 {
+    f32::from_bits((bits as u32) << 16)
+}
+
+/// Converts an `f32` to a bfloat16, rounding to nearest and ties to even.
+///
+/// NaN payloads are truncated to their upper bits and the result is always a quiet NaN.
+pub fn f32_to_bf16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    if value.is_nan() {
+        return (bits >> 16) as u16 | 0x0040;
+    }
+    round_shift(bits as u64, 16) as u16
+}
+
+/// Converts a slice of IEEE 754 half precision floats to `f32`.
+pub fn f16_to_f32_slice(bits: &[u16]) -> Vec<f32> {
+    bits.iter().map(|bits| f16_to_f32(*bits)).collect()
+}
+
+/// Converts a slice of `f32` to IEEE 754 half precision floats.
+pub fn f32_to_f16_slice(values: &[f32]) -> Vec<u16> {
+    values.iter().map(|value| f32_to_f16(*value)).collect()
+}
+
+/// Converts a slice of bfloat16 values to `f32`.
+pub fn bf16_to_f32_slice(bits: &[u16]) -> Vec<f32> {
+    bits.iter().map(|bits| bf16_to_f32(*bits)).collect()
+}
+
+/// Converts a slice of `f32` to bfloat16 values.
+pub fn f32_to_bf16_slice(values: &[f32]) -> Vec<u16> {
+    values.iter().map(|value| f32_to_bf16(*value)).collect()
+}
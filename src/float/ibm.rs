@@ -0,0 +1,82 @@
+use super::{compose_f64, decompose_f64, round_shift};
+use crate::{Error, Result};
+
+/// Converts an IBM System/360 single precision hex float to `f64`, which is always exact.
+///
+/// Unnormalized fractions are converted by value.
+pub fn ibm32_to_f64(bits: u32) -> f64 {
+    let exp = ((bits >> 24) & 0x7f) as i64;
+    compose_f64(
+        bits >> 31 == 1,
+        (bits & 0xff_ffff) as u64,
+        4 * (exp - 64) - 24,
+    )
+}
+
+/// Converts an IBM System/360 double precision hex float to `f64`,
+/// rounding the 56-bit fraction to nearest and ties to even.
+///
+/// Unnormalized fractions are converted by value.
+pub fn ibm64_to_f64(bits: u64) -> f64 {
+    let exp = ((bits >> 56) & 0x7f) as i64;
+    compose_f64(
+        bits >> 63 == 1,
+        bits & 0xff_ffff_ffff_ffff,
+        4 * (exp - 64) - 56,
+    )
+}
+
+/// Converts an `f64` to an IBM System/360 single precision hex float,
+/// rounding the fraction to nearest and ties to even.
+///
+/// Fails for infinity, NaN and values beyond the largest hex float (about `7.2e75`),
+/// values below the smallest normalized hex float (about `5.4e-79`) become zero.
+pub fn f64_to_ibm32(value: f64) -> Result<u32> {
+    Ok(to_ibm(value, 24, "ibm32")? as u32)
+}
+
+/// Converts an `f64` to an IBM System/360 double precision hex float, which is exact
+/// for normalized results.
+///
+/// Fails for infinity, NaN and values beyond the largest hex float (about `7.2e75`),
+/// values below the smallest normalized hex float (about `5.4e-79`) become zero.
+pub fn f64_to_ibm64(value: f64) -> Result<u64> {
+    to_ibm(value, 56, "ibm64")
+}
+
+fn to_ibm(value: f64, frac_bits: u32, type_name: &'static str) -> Result<u64> {
+    let sign = (value.is_sign_negative() as u64) << (frac_bits + 7);
+    if !value.is_finite() {
+        return Err(Error::Unrepresentable {
+            offset: None,
+            value,
+            type_name,
+        });
+    }
+    if value == 0.0 {
+        return Ok(sign);
+    }
+
+    // the value is `frac / 2^frac_bits * 16^hex` with the first hex digit of `frac` not zero
+    let (_, man, exp) = decompose_f64(value);
+    let mut hex = (exp + 3).div_euclid(4);
+    let shift = 4 * hex - exp + 53 - frac_bits as i64;
+    let mut frac = match shift {
+        1.. => round_shift(man, shift as u32),
+        _ => man << -shift,
+    };
+    if frac >> frac_bits != 0 {
+        frac >>= 4;
+        hex += 1;
+    }
+
+    match hex + 64 {
+        128.. => Err(Error::Unrepresentable {
+            offset: None,
+            value,
+            type_name,
+        }),
+        ..0 => Ok(sign),
+        exp => Ok(sign | (exp as u64) << frac_bits | frac),
+    }
+}
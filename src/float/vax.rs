@@ -0,0 +1,160 @@
+use super::{compose_f64, decompose_f64, round_shift};
+use crate::{Error, Result};
+
+/// Layout of a VAX float, the fraction has a hidden leading bit.
+struct Format {
+    type_name: &'static str,
+    size: usize,
+    exp_bits: u32,
+    frac_bits: u32,
+    bias: i64,
+}
+
+const F: Format = Format {
+    type_name: "vax_f",
+    size: 4,
+    exp_bits: 8,
+    frac_bits: 23,
+    bias: 128,
+};
+
+const D: Format = Format {
+    type_name: "vax_d",
+    size: 8,
+    exp_bits: 8,
+    frac_bits: 55,
+    bias: 128,
+};
+
+const G: Format = Format {
+    type_name: "vax_g",
+    size: 8,
+    exp_bits: 11,
+    frac_bits: 52,
+    bias: 1024,
+};
+
+/// Converts the 4 bytes of a VAX F_floating value to `f64`, which is always exact.
+///
+/// Fails for the reserved operand (negative sign with a zero exponent).
+pub fn vax_f_to_f64(bytes: [u8; 4]) -> Result<f64> {
+    decode(&bytes, &F)
+}
+
+/// Converts the 8 bytes of a VAX D_floating value to `f64`,
+/// rounding the 56-bit mantissa to nearest and ties to even.
+///
+/// Fails for the reserved operand (negative sign with a zero exponent).
+pub fn vax_d_to_f64(bytes: [u8; 8]) -> Result<f64> {
+    decode(&bytes, &D)
+}
+
+/// Converts the 8 bytes of a VAX G_floating value to `f64`.
+///
+/// This is exact except for the smallest values, which are rounded to `f64` subnormals.
+/// Fails for the reserved operand (negative sign with a zero exponent).
+pub fn vax_g_to_f64(bytes: [u8; 8]) -> Result<f64> {
+    decode(&bytes, &G)
+}
+
+/// Converts an `f64` to the 4 bytes of a VAX F_floating value,
+/// rounding to nearest and ties to even.
+///
+/// Fails for infinity, NaN and values beyond about `1.7e38`,
+/// values below about `2.9e-39` become zero as VAX floats have no subnormals.
+pub fn f64_to_vax_f(value: f64) -> Result<[u8; 4]> {
+    let mut bytes = [0; 4];
+    encode(value, &F, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Converts an `f64` to the 8 bytes of a VAX D_floating value, which is exact for all values in range.
+///
+/// Fails for infinity, NaN and values beyond about `1.7e38`,
+/// values below about `2.9e-39` become zero as VAX floats have no subnormals.
+pub fn f64_to_vax_d(value: f64) -> Result<[u8; 8]> {
+    let mut bytes = [0; 8];
+    encode(value, &D, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Converts an `f64` to the 8 bytes of a VAX G_floating value, which is exact for all values in range.
+///
+/// Fails for infinity, NaN and values beyond about `9.0e307`,
+/// values below about `5.6e-309` become zero as VAX floats have no subnormals.
+pub fn f64_to_vax_g(value: f64) -> Result<[u8; 8]> {
+    let mut bytes = [0; 8];
+    encode(value, &G, &mut bytes)?;
+    Ok(bytes)
+}
+
+// VAX floats are stored as little endian 16-bit words, with the word holding the sign first
+fn decode(bytes: &[u8], format: &Format) -> Result<f64> {
+    let bits = bytes.chunks_exact(2).fold(0, |bits, word| {
+        bits << 16 | u16::from_le_bytes([word[0], word[1]]) as u64
+    });
+
+    let negative = bits >> (format.size * 8 - 1) == 1;
+    let exp = ((bits >> format.frac_bits) & ((1 << format.exp_bits) - 1)) as i64;
+    let frac = bits & ((1 << format.frac_bits) - 1);
+
+    match (exp, negative) {
+        (0, false) => Ok(0.0),
+        (0, true) => Err(Error::InvalidEncoding {
+            offset: None,
+            type_name: format.type_name,
+            reason: "reserved operand",
+        }),
+        _ => Ok(compose_f64(
+            negative,
+            1 << format.frac_bits | frac,
+            exp - format.bias - format.frac_bits as i64 - 1,
+        )),
+    }
+}
+
+fn encode(value: f64, format: &Format, bytes: &mut [u8]) -> Result<()> {
+    let bits = encode_bits(value, format)?;
+    for (index, word) in bytes.chunks_exact_mut(2).rev().enumerate() {
+        word.copy_from_slice(&((bits >> (index * 16)) as u16).to_le_bytes());
+    }
+    Ok(())
+}
+
+fn encode_bits(value: f64, format: &Format) -> Result<u64> {
+    let unrepresentable = Error::Unrepresentable {
+        offset: None,
+        value,
+        type_name: format.type_name,
+    };
+    if !value.is_finite() {
+        return Err(unrepresentable);
+    }
+    // a negative zero would be the reserved operand
+    if value == 0.0 {
+        return Ok(0);
+    }
+
+    // the value is `0.1frac * 2^(exp - bias)`
+    let (negative, man, exp) = decompose_f64(value);
+    let bits = format.frac_bits + 1;
+    let mut exp = exp + format.bias;
+    let mut man = match bits {
+        53.. => man << (bits - 53),
+        _ => round_shift(man, 53 - bits),
+    };
+    if man >> bits != 0 {
+        man >>= 1;
+        exp += 1;
+    }
+
+    if exp >= 1 << format.exp_bits {
+        return Err(unrepresentable);
+    }
+    if exp <= 0 {
+        return Ok(0);
+    }
+    Ok((negative as u64) << (format.size * 8 - 1)
+        | (exp as u64) << format.frac_bits
+        | (man & ((1 << format.frac_bits) - 1)))
+}
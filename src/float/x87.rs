@@ -0,0 +1,83 @@
+use super::{compose_f64, decompose_f64};
+use crate::Endianess;
+
+/// Converts an x87 80-bit extended precision float to `f64`, rounding to nearest and ties to even.
+///
+/// The value is given as its sign and exponent word and its 64-bit mantissa
+/// with the explicit integer bit. Values too large for `f64` become infinity,
+/// values too small become subnormals or zero.
+/// NaN payloads are truncated to their upper bits, denormals and unnormals are converted by value.
+pub fn x87_to_f64(sign_exp: u16, mantissa: u64) -> f64 {
+    let negative = sign_exp & 0x8000 != 0;
+    let exp = (sign_exp & 0x7fff) as i64;
+
+    if exp == 0x7fff {
+        let sign = (negative as u64) << 63;
+        let frac = mantissa & !(1 << 63);
+        return match frac >> 11 {
+            0 if frac == 0 => f64::from_bits(sign | 0x7ff0_0000_0000_0000),
+            0 => f64::from_bits(sign | 0x7ff8_0000_0000_0000),
+            payload => f64::from_bits(sign | 0x7ff0_0000_0000_0000 | payload),
+        };
+    }
+
+    // denormals use the same scale as the smallest normal exponent
+    compose_f64(negative, mantissa, exp.max(1) - 16383 - 63)
+}
+
+/// Converts an `f64` to an x87 80-bit extended precision float, which is always exact.
+///
+/// Returns the sign and exponent word and the 64-bit mantissa with the explicit integer bit.
+pub fn f64_to_x87(value: f64) -> (u16, u64) {
+    let sign = ((value.to_bits() >> 48) & 0x8000) as u16;
+
+    if value.is_nan() {
+        let payload = value.to_bits() & 0xf_ffff_ffff_ffff;
+        return (sign | 0x7fff, 1 << 63 | payload << 11);
+    }
+    if value.is_infinite() {
+        return (sign | 0x7fff, 1 << 63);
+    }
+    if value == 0.0 {
+        return (sign, 0);
+    }
+
+    let (_, man, exp) = decompose_f64(value);
+    (sign | (exp - 1 + 16383) as u16, man << 11)
+}
+
+/// Converts the 10 bytes of an x87 float, the little endian layout has the mantissa first.
+pub(crate) fn f80_from_bytes(bytes: [u8; 10], endianess: Endianess) -> f64 {
+    let (mut sign_exp, mut mantissa) = ([0; 2], [0; 8]);
+    if is_little(endianess) {
+        mantissa.copy_from_slice(&bytes[..8]);
+        sign_exp.copy_from_slice(&bytes[8..]);
+        x87_to_f64(u16::from_le_bytes(sign_exp), u64::from_le_bytes(mantissa))
+    } else {
+        sign_exp.copy_from_slice(&bytes[..2]);
+        mantissa.copy_from_slice(&bytes[2..]);
+        x87_to_f64(u16::from_be_bytes(sign_exp), u64::from_be_bytes(mantissa))
+    }
+}
+
+/// Converts a value to the 10 bytes of an x87 float.
+pub(crate) fn f80_to_bytes(value: f64, endianess: Endianess) -> [u8; 10] {
+    let (sign_exp, mantissa) = f64_to_x87(value);
+    let mut bytes = [0; 10];
+    if is_little(endianess) {
+        bytes[..8].copy_from_slice(&mantissa.to_le_bytes());
+        bytes[8..].copy_from_slice(&sign_exp.to_le_bytes());
+    } else {
+        bytes[..2].copy_from_slice(&sign_exp.to_be_bytes());
+        bytes[2..].copy_from_slice(&mantissa.to_be_bytes());
+    }
+    bytes
+}
+
+fn is_little(endianess: Endianess) -> bool {
+    match endianess {
+        Endianess::Little => true,
+        Endianess::Big => false,
+        Endianess::Native => cfg!(target_endian = "little"),
+    }
+}
@@ -0,0 +1,121 @@
+//! Cryptographic hashes that can be used with the checksum adapters.
+//!
+//! The hashes implement `Checksum`, so `ChecksumReader` and `ChecksumWriter` hash all bytes passing through them
+//! and `ReadValAt::hash_at` hashes a byte range. MD5 and SHA-1 are broken and only meant for legacy formats.
+
+use crate::checksum::Checksum;
+
+mod md5;
+mod sha1;
+mod sha256;
+
+pub use md5::Md5;
+pub use sha1::Sha1;
+pub use sha256::Sha256;
+
+/// Collects bytes into 64 byte blocks and pads the last one as MD5 and SHA do.
+#[derive(Clone, Copy)]
+struct Blocks {
+    block: [u8; 64],
+    len: usize,
+    total: u64,
+}
+
+impl Blocks {
+    const fn new() -> Self {
+        Self {
+            block: [0; 64],
+            len: 0,
+            total: 0,
+        }
+    }
+
+    fn update(&mut self, mut bytes: &[u8], mut compress: impl FnMut(&[u8; 64])) {
+        self.total += bytes.len() as u64;
+        while !bytes.is_empty() {
+            let len = bytes.len().min(64 - self.len);
+            self.block[self.len..self.len + len].copy_from_slice(&bytes[..len]);
+            self.len += len;
+            bytes = &bytes[len..];
+            if self.len == 64 {
+                compress(&self.block);
+                self.len = 0;
+            }
+        }
+    }
+
+    /// Appends the padding and the message length in bits.
+    fn finish(mut self, big_endian: bool, mut compress: impl FnMut(&[u8; 64])) {
+        let bits = self.total.wrapping_mul(8);
+        let len = match big_endian {
+            true => bits.to_be_bytes(),
+            false => bits.to_le_bytes(),
+        };
+
+        let padding = if self.len < 56 {
+            56 - self.len
+        } else {
+            120 - self.len
+        };
+        let mut tail = [0; 72];
+        tail[0] = 0x80;
+        tail[padding..padding + 8].copy_from_slice(&len);
+        self.update(&tail[..padding + 8], &mut compress);
+    }
+}
+
+macro_rules! hash {
+    ($name:ident, $size:literal, $init:expr, $big_endian:literal) => {
+        impl $name {
+            pub const fn new() -> Self {
+                Self {
+                    state: $init,
+                    blocks: crate::hash::Blocks::new(),
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl crate::checksum::Checksum for $name {
+            type Output = [u8; $size];
+
+            fn update(&mut self, bytes: &[u8]) {
+                let state = &mut self.state;
+                self.blocks.update(bytes, |block| compress(state, block));
+            }
+
+            fn value(&self) -> [u8; $size] {
+                let mut state = self.state;
+                self.blocks
+                    .finish($big_endian, |block| compress(&mut state, block));
+
+                let mut digest = [0; $size];
+                for (chunk, word) in digest.chunks_exact_mut(4).zip(state) {
+                    chunk.copy_from_slice(&match $big_endian {
+                        true => word.to_be_bytes(),
+                        false => word.to_le_bytes(),
+                    });
+                }
+                digest
+            }
+
+            fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+    };
+}
+
+use hash;
+
+/// Hashes all bytes with a new instance of `H`.
+pub fn digest<H: Checksum + Default>(bytes: &[u8]) -> H::Output {
+    let mut hash = H::default();
+    hash.update(bytes);
+    hash.value()
+}
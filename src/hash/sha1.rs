@@ -0,0 +1,46 @@
+use super::{Blocks, hash};
+
+/// SHA-1 hash (FIPS 180-4), only use it to verify legacy checksums.
+#[derive(Clone, Copy)]
+pub struct Sha1 {
+    state: [u32; 5],
+    blocks: Blocks,
+}
+
+hash!(
+    Sha1,
+    20,
+    [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0],
+    true
+);
+
+fn compress(state: &mut [u32; 5], block: &[u8; 64]) {
+    let mut words = [0u32; 80];
+    for i in 0..80 {
+        words[i] = match i {
+            0..16 => u32::from_be_bytes(block[i * 4..i * 4 + 4].try_into().unwrap()),
+            _ => (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1),
+        };
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, word) in words.iter().enumerate() {
+        let (f, k) = match i / 20 {
+            0 => (b & c | !b & d, 0x5a827999),
+            1 => (b ^ c ^ d, 0x6ed9eba1),
+            2 => (b & c | b & d | c & d, 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+        (a, b, c, d, e) = (temp, a, b.rotate_left(30), c, d);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e]) {
+        *word = word.wrapping_add(value);
+    }
+}
@@ -1,15 +1,48 @@
+pub mod checksum;
+pub mod compress;
+mod counting;
+mod decimal;
+mod dump;
 mod dynamic;
+pub mod encoding;
+mod endian;
 mod error;
+mod fixed;
+mod flags;
+pub mod float;
+pub mod hash;
+mod limit;
+mod odd;
+pub mod patch;
+mod peek;
+pub mod pod;
 mod primitive;
 mod read;
+mod search;
+mod tagged;
+mod trace;
 mod types;
 #[cfg(feature = "vli")]
 mod variable;
 mod write;
 
+pub use checksum::{Checksum, ChecksumReader, ChecksumWriter};
+pub use counting::{CountingReader, CountingWriter};
+pub use dump::{HexDump, Span};
 pub use dynamic::Dynamic;
-pub use error::{Error, Result};
+pub use endian::Endian;
+pub use error::{Error, Result, ResultExt};
+pub use fixed::{
+    F2Dot14, Fixed, Fixed2_30, Fixed8_8, Fixed16_16, FixedBits, Q15, Q31, UFixed16_16,
+};
+pub use flags::Flags;
+pub use limit::Limited;
+pub use peek::{PeekVal, Pushback};
+pub use pod::Pod;
 pub use primitive::Primitive;
 pub use read::{at::ReadValAt, val::ReadVal};
+pub use search::Pattern;
+pub use tagged::{Be, ByteOrder, F32, F64, I16, I32, I64, I128, Le, U16, U32, U64, U128};
+pub use trace::{TraceEntry, Tracer};
 pub use types::*;
 pub use write::{at::WriteValAt, val::WriteVal};
@@ -0,0 +1,158 @@
+use crate::{Dynamic, Error, Pushback, Result};
+use std::any::type_name;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Initial allocation size for dynamic reads, the buffer only grows as data actually arrives.
+pub(crate) const CHUNK: usize = 8192;
+
+/// Reads exactly `len` bytes without allocating them up front,
+/// so a truncated stream fails before a large buffer is allocated.
+pub(crate) fn read_bytes<R: Read + ?Sized>(reader: &mut R, len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(len.min(CHUNK));
+    while buf.len() < len {
+        let start = buf.len();
+        buf.resize(start + (len - start).min(CHUNK), 0);
+        reader.read_exact(&mut buf[start..])?;
+    }
+    Ok(buf)
+}
+
+/// Wrapper for `Read` that limits how many bytes can be read from untrusted input.
+///
+/// The total limit applies to every read going through the wrapper.
+/// The call limit applies to the `read_dynamic`, `read_vec` and `read_str` methods of this wrapper,
+/// which also check the requested length against the remaining budget before allocating anything.
+/// Generic code that only knows `ReadVal` is bound by the total limit alone.
+///
+/// Reading at the limit returns `Ok(0)` at the end of the inner stream and fails otherwise,
+/// which takes one byte past the limit from the inner reader. `into_inner` gives it back.
+pub struct Limited<R: Read> {
+    inner: R,
+    limit: u64,
+    call_limit: Option<usize>,
+    consumed: u64,
+    /// Byte read past the limit to tell it apart from the end of the stream.
+    overflow: Option<u8>,
+}
+
+impl<R: Read> Limited<R> {
+    /// Creates a new reader that allows reading at most `limit` bytes in total.
+    pub fn new(inner: R, limit: u64) -> Self {
+        Self {
+            inner,
+            limit,
+            call_limit: None,
+            consumed: 0,
+            overflow: None,
+        }
+    }
+
+    /// Sets the maximum length of a single dynamic read.
+    pub fn with_call_limit(mut self, call_limit: usize) -> Self {
+        self.call_limit = Some(call_limit);
+        self
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    /// Returns the number of bytes that can still be read.
+    pub fn remaining(&self) -> u64 {
+        self.limit - self.consumed
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// **Note:** if a read failed at the limit, the inner reader is one byte ahead, `into_inner` gives it back.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, with the byte taken when a read failed at the limit pushed back.
+    pub fn into_inner(self) -> Pushback<R> {
+        let mut reader = Pushback::new(self.inner);
+        reader.unread(self.overflow.as_slice());
+        reader
+    }
+
+    /// Reads a dynamic value after checking its length against the limits.
+    pub fn read_dynamic<T: Dynamic>(&mut self, len: usize) -> Result<T> {
+        if let Some(call_limit) = self.call_limit.filter(|limit| len > *limit) {
+            return Err(Error::LimitExceeded {
+                offset: None,
+                requested: len as u64,
+                limit: call_limit as u64,
+            });
+        }
+        if len as u64 > self.remaining() {
+            return Err(Error::LimitExceeded {
+                offset: None,
+                requested: len as u64,
+                limit: self.remaining(),
+            });
+        }
+
+        let buf =
+            read_bytes(self, len).map_err(|e| Error::for_value(e, None, type_name::<T>(), len))?;
+        T::from_bytes(buf)
+    }
+
+    /// Typed wrapper around `read_dynamic`.
+    pub fn read_vec(&mut self, len: usize) -> Result<Vec<u8>> {
+        self.read_dynamic(len)
+    }
+
+    /// Typed wrapper around `read_dynamic`.
+    pub fn read_str(&mut self, len: usize) -> Result<String> {
+        self.read_dynamic(len)
+    }
+}
+
+impl<R: Read> Read for Limited<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining() == 0 {
+            if self.overflow.is_none() {
+                let mut byte = [0];
+                if self.inner.read(&mut byte)? == 0 {
+                    return Ok(0);
+                }
+                self.overflow = Some(byte[0]);
+            }
+            return Err(Error::LimitExceeded {
+                offset: None,
+                requested: buf.len() as u64,
+                limit: self.limit,
+            }
+            .into());
+        }
+
+        let len = buf
+            .len()
+            .min(self.remaining().try_into().unwrap_or(usize::MAX));
+        let read = self.inner.read(&mut buf[..len])?;
+        self.consumed += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read + Seek> Seek for Limited<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        // the inner reader is one byte ahead if a read failed at the limit
+        let pos = match (pos, self.overflow) {
+            (SeekFrom::Current(offset), Some(_)) => SeekFrom::Current(offset - 1),
+            (pos, _) => pos,
+        };
+        let pos = self.inner.seek(pos)?;
+        self.overflow = None;
+        Ok(pos)
+    }
+}
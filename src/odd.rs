@@ -0,0 +1,120 @@
+use crate::{Endianess, Error, Result};
+use std::io::{Read, Write};
+
+fn type_name(bytes: usize, signed: bool) -> &'static str {
+    const UNSIGNED: [&str; 8] = ["u8", "u16", "u24", "u32", "u40", "u48", "u56", "u64"];
+    const SIGNED: [&str; 8] = ["i8", "i16", "i24", "i32", "i40", "i48", "i56", "i64"];
+    match signed {
+        false => UNSIGNED[bytes - 1],
+        true => SIGNED[bytes - 1],
+    }
+}
+
+fn check_width(bytes: usize) -> Result<()> {
+    match bytes {
+        1..=8 => Ok(()),
+        _ => Err(Error::OutOfRange {
+            offset: None,
+            value: bytes as i128,
+            type_name: "integer width",
+        }),
+    }
+}
+
+fn is_little(endianess: Endianess) -> bool {
+    match endianess {
+        Endianess::Little => true,
+        Endianess::Big => false,
+        Endianess::Native => cfg!(target_endian = "little"),
+    }
+}
+
+fn read_bits<R: Read + ?Sized>(
+    reader: &mut R,
+    bytes: usize,
+    endianess: Endianess,
+    signed: bool,
+) -> Result<u64> {
+    check_width(bytes)?;
+    let mut buf = [0; 8];
+    reader
+        .read_exact(&mut buf[..bytes])
+        .map_err(|e| Error::for_value(e, None, type_name(bytes, signed), bytes))?;
+
+    Ok(match is_little(endianess) {
+        true => u64::from_le_bytes(buf),
+        false => u64::from_be_bytes(buf) >> ((8 - bytes) * 8),
+    })
+}
+
+fn write_bits<W: Write + ?Sized>(
+    writer: &mut W,
+    bytes: usize,
+    endianess: Endianess,
+    value: u64,
+    signed: bool,
+) -> Result<()> {
+    let buf = value.to_le_bytes();
+    let result = match is_little(endianess) {
+        true => writer.write_all(&buf[..bytes]),
+        false => writer.write_all(&value.to_be_bytes()[8 - bytes..]),
+    };
+    result.map_err(|e| Error::for_value(e, None, type_name(bytes, signed), bytes))
+}
+
+/// Reads an unsigned integer with a width of 1 to 8 bytes.
+pub(crate) fn read_uint<R: Read + ?Sized>(
+    reader: &mut R,
+    bytes: usize,
+    endianess: Endianess,
+) -> Result<u64> {
+    read_bits(reader, bytes, endianess, false)
+}
+
+/// Reads a signed integer with a width of 1 to 8 bytes and extends its sign.
+pub(crate) fn read_int<R: Read + ?Sized>(
+    reader: &mut R,
+    bytes: usize,
+    endianess: Endianess,
+) -> Result<i64> {
+    let bits = read_bits(reader, bytes, endianess, true)?;
+    let shift = (8 - bytes as u32) * 8;
+    Ok((bits << shift) as i64 >> shift)
+}
+
+/// Writes an unsigned integer with a width of 1 to 8 bytes, checking that it fits.
+pub(crate) fn write_uint<W: Write + ?Sized>(
+    writer: &mut W,
+    bytes: usize,
+    endianess: Endianess,
+    value: u64,
+) -> Result<()> {
+    check_width(bytes)?;
+    if bytes < 8 && value >> (bytes * 8) != 0 {
+        return Err(Error::OutOfRange {
+            offset: None,
+            value: value as i128,
+            type_name: type_name(bytes, false),
+        });
+    }
+    write_bits(writer, bytes, endianess, value, false)
+}
+
+/// Writes a signed integer with a width of 1 to 8 bytes, checking that it fits.
+pub(crate) fn write_int<W: Write + ?Sized>(
+    writer: &mut W,
+    bytes: usize,
+    endianess: Endianess,
+    value: i64,
+) -> Result<()> {
+    check_width(bytes)?;
+    let shift = (8 - bytes as u32) * 8;
+    if (value << shift) >> shift != value {
+        return Err(Error::OutOfRange {
+            offset: None,
+            value: value as i128,
+            type_name: type_name(bytes, true),
+        });
+    }
+    write_bits(writer, bytes, endianess, value as u64, true)
+}
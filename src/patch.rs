@@ -0,0 +1,218 @@
+//! Binary diffs between two streams and patches in a compact format, IPS and BPS.
+//!
+//! `diff` returns the differing ranges, `Patch` holds the new bytes for them and reproduces
+//! the new version from the old one with `Patch::apply`.
+//! BPS patches describe the whole target and are created and applied with `write_bps` and `apply_bps`.
+
+use crate::{Error, ReadVal, ReadValAt, Result, WriteVal};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+
+mod bps;
+mod ips;
+
+pub use bps::{apply_bps, write_bps};
+
+const CHUNK: usize = 65536;
+const MAGIC: &[u8; 4] = b"DHP1";
+
+fn invalid(type_name: &'static str, reason: &'static str) -> Error {
+    Error::InvalidEncoding {
+        offset: None,
+        type_name,
+        reason,
+    }
+}
+
+/// Returns the length of the stream without moving the cursor.
+fn stream_len<S: Seek + ?Sized>(stream: &mut S) -> Result<u64> {
+    let pos_before = stream.stream_position()?;
+    let len = stream.seek(SeekFrom::End(0))?;
+    stream.seek(SeekFrom::Start(pos_before))?;
+    Ok(len)
+}
+
+/// Reads as many bytes as possible up to `len` bytes.
+fn read_chunk<R: Read + ?Sized>(reader: &mut R, len: usize) -> Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(len);
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Writes a variable length integer as used by BPS, every value has exactly one encoding.
+fn write_number<W: Write>(writer: &mut W, mut value: u64) -> Result<()> {
+    loop {
+        let low = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_u8(low | 0x80);
+        }
+        writer.write_u8(low)?;
+        value -= 1;
+    }
+}
+
+/// Reads a variable length integer as used by BPS.
+fn read_number<R: Read>(reader: &mut R) -> Result<u64> {
+    let too_large = || invalid("u64", "variable length integer too large");
+    let (mut value, mut shift) = (0u64, 1u64);
+    loop {
+        let byte = reader.read_u8()?;
+        value = (byte as u64 & 0x7f)
+            .checked_mul(shift)
+            .and_then(|part| value.checked_add(part))
+            .ok_or_else(too_large)?;
+        if byte & 0x80 != 0 {
+            return Ok(value);
+        }
+        shift = shift.checked_mul(128).ok_or_else(too_large)?;
+        value = value.checked_add(shift).ok_or_else(too_large)?;
+    }
+}
+
+/// Compares two streams from their start and returns the ranges where `new` differs from `old`.
+///
+/// Positions past the end of `old` count as different, bytes only present in `old` are not reported.
+/// The cursors are not moved.
+pub fn diff<A, B>(old: &mut A, new: &mut B) -> Result<Vec<Range<u64>>>
+where
+    A: Read + Seek,
+    B: Read + Seek,
+{
+    let (old_before, new_before) = (old.stream_position()?, new.stream_position()?);
+    old.seek(SeekFrom::Start(0))?;
+    new.seek(SeekFrom::Start(0))?;
+    let response = compare(old, new);
+    old.seek(SeekFrom::Start(old_before))?;
+    new.seek(SeekFrom::Start(new_before))?;
+    response
+}
+
+fn compare<A, B>(old: &mut A, new: &mut B) -> Result<Vec<Range<u64>>>
+where
+    A: Read,
+    B: Read,
+{
+    let mut ranges: Vec<Range<u64>> = Vec::new();
+    let mut pos = 0;
+    loop {
+        let new_chunk = read_chunk(new, CHUNK)?;
+        if new_chunk.is_empty() {
+            return Ok(ranges);
+        }
+        let old_chunk = read_chunk(old, new_chunk.len())?;
+        for (i, byte) in new_chunk.iter().enumerate() {
+            if old_chunk.get(i) == Some(byte) {
+                continue;
+            }
+            let offset = pos + i as u64;
+            match ranges.last_mut() {
+                Some(last) if last.end == offset => last.end += 1,
+                _ => ranges.push(offset..offset + 1),
+            }
+        }
+        pos += new_chunk.len() as u64;
+    }
+}
+
+/// Bytes to write at an offset of the target.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Record {
+    pub offset: u64,
+    pub data: Vec<u8>,
+}
+
+/// List of records that turns an old version of a stream into a new one.
+///
+/// The compact format written by `Patch::write` starts with `DHP1`, followed by the truncated
+/// length plus one (0 if not truncated), the record count and every record as offset, length
+/// and data, all numbers use the variable length integers of BPS.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Patch {
+    /// Length the target is cut to, `None` keeps the source length (or more, if records reach past it).
+    pub truncate: Option<u64>,
+    pub records: Vec<Record>,
+}
+
+impl Patch {
+    /// Creates a patch with a record for every range returned by `diff`.
+    ///
+    /// The cursors are not moved.
+    pub fn diff<A, B>(old: &mut A, new: &mut B) -> Result<Self>
+    where
+        A: Read + Seek,
+        B: Read + Seek,
+    {
+        let (old_len, new_len) = (stream_len(old)?, stream_len(new)?);
+        let records = diff(old, new)?
+            .into_iter()
+            .map(|range| {
+                let data =
+                    new.read_vec_at(range.start as usize, (range.end - range.start) as usize)?;
+                Ok(Record {
+                    offset: range.start,
+                    data,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            truncate: (new_len < old_len).then_some(new_len),
+            records,
+        })
+    }
+
+    /// Reads a patch in the compact format.
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self> {
+        if &reader.read_vec(MAGIC.len())?[..] != MAGIC {
+            return Err(invalid("patch", "missing DHP1 header"));
+        }
+        let truncate = read_number(reader)?.checked_sub(1);
+        let count = read_number(reader)?;
+        let mut records = Vec::new();
+        for _ in 0..count {
+            let offset = read_number(reader)?;
+            let len = read_number(reader)?;
+            let data = reader.read_vec(len as usize)?;
+            records.push(Record { offset, data });
+        }
+        Ok(Self { truncate, records })
+    }
+
+    /// Writes the patch in the compact format.
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(MAGIC)?;
+        write_number(writer, self.truncate.map_or(0, |len| len + 1))?;
+        write_number(writer, self.records.len() as u64)?;
+        for record in &self.records {
+            write_number(writer, record.offset)?;
+            write_number(writer, record.data.len() as u64)?;
+            writer.write_all(&record.data)?;
+        }
+        Ok(())
+    }
+
+    /// Copies the source to the start of the target and writes the records over it.
+    ///
+    /// The target is not truncated, so it should be empty. The cursors are not moved.
+    pub fn apply<S, T>(&self, source: &mut S, target: &mut T) -> Result<()>
+    where
+        S: Read + Seek,
+        T: Write + Seek,
+    {
+        let end = self.truncate.unwrap_or(u64::MAX);
+        let len = stream_len(source)?.min(end);
+        source.copy_to_at(0, len, 0, target)?;
+        let pos_before = target.stream_position()?;
+        let response = self.records.iter().try_for_each(|record| {
+            let len = end
+                .saturating_sub(record.offset)
+                .min(record.data.len() as u64);
+            target.seek(SeekFrom::Start(record.offset))?;
+            target
+                .write_all(&record.data[..len as usize])
+                .map_err(|e| Error::from(e).with_offset(Some(record.offset)))
+        });
+        target.seek(SeekFrom::Start(pos_before))?;
+        response
+    }
+}
@@ -0,0 +1,167 @@
+use super::{diff, invalid, read_number, stream_len, write_number};
+use crate::checksum::{Algorithm, Checksum, Crc32};
+use crate::{ChecksumWriter, Endianess, Error, ReadVal, ReadValAt, Result, WriteVal};
+use std::io::{Read, Seek, Write};
+
+const SOURCE_READ: u64 = 0;
+const TARGET_READ: u64 = 1;
+const SOURCE_COPY: u64 = 2;
+
+fn write_action<W: Write>(writer: &mut W, action: u64, len: u64) -> Result<()> {
+    write_number(writer, (len - 1) << 2 | action)
+}
+
+/// Moves a relative offset by a signed BPS number, the lowest bit is the sign.
+fn relative(base: u64, value: u64) -> Result<u64> {
+    match value & 1 {
+        0 => base.checked_add(value >> 1),
+        _ => base.checked_sub(value >> 1),
+    }
+    .ok_or_else(|| invalid("BPS", "relative offset out of range"))
+}
+
+/// Appends `len` bytes of the source at `pos` to the output, failing if the source is too short.
+fn copy_source<S: Read + Seek>(
+    source: &mut S,
+    pos: u64,
+    len: u64,
+    output: &mut Vec<u8>,
+) -> Result<()> {
+    let before = output.len();
+    source.copy_at(pos, len, output)?;
+    match (output.len() - before) as u64 == len {
+        true => Ok(()),
+        false => Err(invalid("BPS", "copy past the end of the source")),
+    }
+}
+
+/// Writes a BPS patch that turns `source` into `target`.
+///
+/// Unchanged bytes are read from the source and changed bytes are stored in the patch,
+/// matches at other offsets are not searched. The cursors are not moved.
+pub fn write_bps<S, T, W>(source: &mut S, target: &mut T, writer: W) -> Result<()>
+where
+    S: Read + Seek,
+    T: Read + Seek,
+    W: Write,
+{
+    let (source_len, target_len) = (stream_len(source)?, stream_len(target)?);
+    let mut ranges = diff(source, target)?;
+    ranges.push(target_len..target_len);
+
+    let mut writer = ChecksumWriter::new(writer, Crc32::ieee());
+    writer.write_all(b"BPS1")?;
+    write_number(&mut writer, source_len)?;
+    write_number(&mut writer, target_len)?;
+    write_number(&mut writer, 0)?;
+    let mut pos = 0;
+    for range in ranges {
+        if range.start > pos {
+            write_action(&mut writer, SOURCE_READ, range.start - pos)?;
+        }
+        if !range.is_empty() {
+            write_action(&mut writer, TARGET_READ, range.end - range.start)?;
+            target.copy_at(range.start, range.end - range.start, &mut writer)?;
+        }
+        pos = range.end;
+    }
+    writer.write_u32_le(source.checksum_at(0, source_len, Algorithm::Crc32)? as u32)?;
+    writer.write_u32_le(target.checksum_at(0, target_len, Algorithm::Crc32)? as u32)?;
+    writer.write_checksum::<4>(Endianess::Little)
+}
+
+/// Applies a BPS patch to `source` and writes the result to `target`.
+///
+/// The patch and the result are kept in memory, so the CRC-32 of the patch, the source and the
+/// result are all verified before anything is written. The source cursor is not moved.
+pub fn apply_bps<S, R, W>(source: &mut S, patch: &mut R, target: &mut W) -> Result<()>
+where
+    S: Read + Seek,
+    R: Read,
+    W: Write,
+{
+    let mut data = Vec::new();
+    patch.read_to_end(&mut data)?;
+    if data.len() < 19 {
+        return Err(invalid("BPS", "patch too short"));
+    }
+    let footer = data.len() - 12;
+    let stored = |i: usize| {
+        let bytes = &data[footer + i * 4..footer + i * 4 + 4];
+        u32::from_le_bytes(bytes.try_into().unwrap()) as u64
+    };
+    let mismatch = |offset: usize, type_name, actual: u64| Error::ChecksumMismatch {
+        offset: Some(offset as u64),
+        type_name,
+        expected: stored((offset - footer) / 4),
+        actual,
+    };
+
+    let mut crc = Crc32::ieee();
+    crc.update(&data[..footer + 8]);
+    if crc.value() as u64 != stored(2) {
+        return Err(mismatch(footer + 8, "BPS patch CRC", crc.value() as u64));
+    }
+
+    let mut reader = &data[..footer];
+    if reader.read_vec(4)? != b"BPS1" {
+        return Err(invalid("BPS", "missing BPS1 header"));
+    }
+    let source_len = read_number(&mut reader)?;
+    let target_len = read_number(&mut reader)?;
+    let metadata_len = read_number(&mut reader)?;
+    reader.read_vec(metadata_len as usize)?;
+    let actual_len = stream_len(source)?;
+    if actual_len != source_len {
+        return Err(Error::SizeMismatch {
+            offset: None,
+            type_name: "BPS source",
+            expected: source_len as usize,
+            actual: actual_len as usize,
+        });
+    }
+    let actual = source.checksum_at(0, source_len, Algorithm::Crc32)?;
+    if actual != stored(0) {
+        return Err(mismatch(footer, "BPS source CRC", actual));
+    }
+
+    let mut output = Vec::new();
+    let (mut source_offset, mut target_offset) = (0u64, 0u64);
+    while !reader.is_empty() {
+        let action = read_number(&mut reader)?;
+        let len = (action >> 2) + 1;
+        if output.len() as u64 + len > target_len {
+            return Err(invalid("BPS", "action past the end of the target"));
+        }
+        match action & 3 {
+            SOURCE_READ => copy_source(source, output.len() as u64, len, &mut output)?,
+            TARGET_READ => output.extend(reader.read_vec(len as usize)?),
+            SOURCE_COPY => {
+                source_offset = relative(source_offset, read_number(&mut reader)?)?;
+                copy_source(source, source_offset, len, &mut output)?;
+                source_offset += len;
+            }
+            _ => {
+                target_offset = relative(target_offset, read_number(&mut reader)?)?;
+                if target_offset >= output.len() as u64 {
+                    return Err(invalid("BPS", "copy past the end of the target"));
+                }
+                // byte by byte, the copy may overlap the bytes it produces
+                for i in target_offset..target_offset + len {
+                    output.push(output[i as usize]);
+                }
+                target_offset += len;
+            }
+        }
+    }
+    if output.len() as u64 != target_len {
+        return Err(invalid("BPS", "actions don't fill the target"));
+    }
+    crc.reset();
+    crc.update(&output);
+    if crc.value() as u64 != stored(1) {
+        return Err(mismatch(footer + 4, "BPS target CRC", crc.value() as u64));
+    }
+    target.write_all(&output)?;
+    Ok(())
+}
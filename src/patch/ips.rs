@@ -0,0 +1,93 @@
+use super::{Patch, Record, invalid};
+use crate::{Endianess, ReadVal, Result, WriteVal};
+use std::io::{Read, Write};
+
+/// Offset that marks the end of the records, it can't be used as a record offset.
+const EOF: u32 = 0x454f46;
+/// Shortest run that is written as an RLE record, shorter runs are cheaper as plain bytes.
+const MIN_RUN: usize = 9;
+const MAX_LEN: usize = u16::MAX as usize;
+
+fn write_offset<W: Write>(writer: &mut W, offset: u64) -> Result<()> {
+    if offset == EOF as u64 {
+        return Err(invalid("IPS", "record offset collides with the EOF marker"));
+    }
+    writer.write_uint_n(3, Endianess::Big, offset)
+}
+
+fn write_bytes<W: Write>(writer: &mut W, offset: u64, data: &[u8]) -> Result<()> {
+    for (i, chunk) in data.chunks(MAX_LEN).enumerate() {
+        write_offset(writer, offset + (i * MAX_LEN) as u64)?;
+        writer.write_u16_be(chunk.len() as u16)?;
+        writer.write_all(chunk)?;
+    }
+    Ok(())
+}
+
+impl Patch {
+    /// Reads an IPS patch, including the optional truncated length after `EOF`.
+    pub fn read_ips<R: Read>(reader: &mut R) -> Result<Self> {
+        if &reader.read_vec(5)?[..] != b"PATCH" {
+            return Err(invalid("IPS", "missing PATCH header"));
+        }
+        let mut records = Vec::new();
+        loop {
+            let offset = reader.read_u24_be()?;
+            if offset == EOF {
+                break;
+            }
+            let data = match reader.read_u16_be()? {
+                0 => {
+                    let count = reader.read_u16_be()?;
+                    vec![reader.read_u8()?; count as usize]
+                }
+                len => reader.read_vec(len as usize)?,
+            };
+            records.push(Record {
+                offset: offset as u64,
+                data,
+            });
+        }
+        let mut truncate = Vec::new();
+        reader.take(3).read_to_end(&mut truncate)?;
+        let truncate = match truncate[..] {
+            [] => None,
+            [a, b, c] => Some(u32::from_be_bytes([0, a, b, c]) as u64),
+            _ => return Err(invalid("IPS", "incomplete truncated length")),
+        };
+        Ok(Self { truncate, records })
+    }
+
+    /// Writes the patch as IPS, runs of at least 9 equal bytes become RLE records.
+    ///
+    /// Fails for offsets that don't fit into 24 bits and for offset `0x454F46`, which reads as `EOF`.
+    pub fn write_ips<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(b"PATCH")?;
+        for record in &self.records {
+            let data = &record.data;
+            let (mut start, mut i) = (0, 0);
+            while i < data.len() {
+                let run = data[i..]
+                    .iter()
+                    .take(MAX_LEN)
+                    .take_while(|byte| **byte == data[i])
+                    .count();
+                if run >= MIN_RUN {
+                    write_bytes(writer, record.offset + start as u64, &data[start..i])?;
+                    write_offset(writer, record.offset + i as u64)?;
+                    writer.write_u16_be(0)?;
+                    writer.write_u16_be(run as u16)?;
+                    writer.write_u8(data[i])?;
+                    start = i + run;
+                }
+                i += run;
+            }
+            write_bytes(writer, record.offset + start as u64, &data[start..])?;
+        }
+        writer.write_all(b"EOF")?;
+        if let Some(len) = self.truncate {
+            writer.write_uint_n(3, Endianess::Big, len)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{Endianess, Error, Primitive, Result, limit::CHUNK};
+use std::any::type_name;
+use std::collections::VecDeque;
+use std::io::{
+    ErrorKind::{Interrupted, UnexpectedEof},
+    Read, Seek,
+    SeekFrom::Start as SeekPos,
+};
+
+macro_rules! peek_primitive {
+    ($fn_name:ident, $read_fn_name:ident) => {
+        /// Peeks a primitive value using the specified byte order without advancing the reader.
+        ///
+        /// It's recommended to use the typed wrappers like `peek_u8` instead of this method for cleaner code.
+        fn $fn_name<T: Primitive<U, S>, U, const S: usize>(&mut self) -> Result<T> {
+            let mut buf = [0; S];
+            self.peek_exact(&mut buf)
+                .map_err(|e| Error::for_value(e, None, type_name::<T>(), S))?;
+            T::$read_fn_name(buf)
+        }
+    };
+}
+
+macro_rules! peek_primitive_typed {
+    ($fn_name:ident, $return_type:ty, $peek_fn:ident) => {
+        /// Typed wrapper around `peek_ne`, `peek_le`, or `peek_be`.
+        fn $fn_name(&mut self) -> Result<$return_type> {
+            self.$peek_fn()
+        }
+    };
+
+    ($fn_name:ident, $return_type:ty) => {
+        /// Typed wrapper around `peek_ne`, `peek_le`, or `peek_be`.
+        fn $fn_name(&mut self, endianess: Endianess) -> Result<$return_type> {
+            use Endianess::*;
+            match endianess {
+                Little => self.peek_le(),
+                Big => self.peek_be(),
+                Native => self.peek_ne(),
+            }
+        }
+    };
+
+    ($fn_name:ident, $return_type:ty, $peek_fn:ident, $const:ident) => {
+        /// Typed wrapper around `peek_ne`, `peek_le`, or `peek_be`.
+        fn $fn_name<const $const: usize>(&mut self) -> Result<$return_type> {
+            self.$peek_fn::<$return_type, $return_type, $const>()
+        }
+    };
+}
+
+macro_rules! peek_dynamic_typed {
+    ($fn_name:ident, $return_type:ty) => {
+        /// Typed wrapper around `peek_dynamic`.
+        fn $fn_name(&mut self, len: usize) -> Result<$return_type> {
+            self.peek_dynamic(len)
+        }
+    };
+}
+
+/// Extension trait for readers that can look ahead without consuming data.
+///
+/// It's implemented for every `Read + Seek` by seeking back after reading,
+/// and for `Pushback`, which buffers the peeked bytes of any `Read`.
+pub trait PeekVal: Read {
+    /// Fills `buf` with the next bytes without advancing the reader.
+    fn peek_exact(&mut self, buf: &mut [u8]) -> Result<()>;
+
+    peek_primitive_typed!(peek_u8, u8, peek_ne);
+
+    peek_primitive_typed!(peek_u16, u16);
+    peek_primitive_typed!(peek_u32, u32);
+    peek_primitive_typed!(peek_u64, u64);
+    peek_primitive_typed!(peek_u128, u128);
+    peek_primitive_typed!(peek_usize, usize);
+
+    peek_primitive_typed!(peek_i8, i8, peek_ne);
+
+    peek_primitive_typed!(peek_i16, i16);
+    peek_primitive_typed!(peek_i32, i32);
+    peek_primitive_typed!(peek_i64, i64);
+    peek_primitive_typed!(peek_i128, i128);
+    peek_primitive_typed!(peek_isize, isize);
+
+    peek_primitive_typed!(peek_f32, f32);
+    peek_primitive_typed!(peek_f64, f64);
+
+    peek_primitive_typed!(peek_u8_array, [u8; S], peek_ne, S);
+
+    peek_primitive_typed!(peek_bool, bool, peek_ne);
+
+    peek_primitive_typed!(peek_char, char);
+
+    peek_dynamic_typed!(peek_vec, Vec<u8>);
+    peek_dynamic_typed!(peek_str, String);
+
+    peek_primitive!(peek_ne, from_ne_bytes);
+    peek_primitive!(peek_le, from_le_bytes);
+    peek_primitive!(peek_be, from_be_bytes);
+
+    peek_primitive_typed!(peek_u16_ne, u16, peek_ne);
+    peek_primitive_typed!(peek_u16_le, u16, peek_le);
+    peek_primitive_typed!(peek_u16_be, u16, peek_be);
+
+    peek_primitive_typed!(peek_u32_ne, u32, peek_ne);
+    peek_primitive_typed!(peek_u32_le, u32, peek_le);
+    peek_primitive_typed!(peek_u32_be, u32, peek_be);
+
+    peek_primitive_typed!(peek_u64_ne, u64, peek_ne);
+    peek_primitive_typed!(peek_u64_le, u64, peek_le);
+    peek_primitive_typed!(peek_u64_be, u64, peek_be);
+
+    peek_primitive_typed!(peek_u128_ne, u128, peek_ne);
+    peek_primitive_typed!(peek_u128_le, u128, peek_le);
+    peek_primitive_typed!(peek_u128_be, u128, peek_be);
+
+    peek_primitive_typed!(peek_usize_ne, usize, peek_ne);
+    peek_primitive_typed!(peek_usize_le, usize, peek_le);
+    peek_primitive_typed!(peek_usize_be, usize, peek_be);
+
+    peek_primitive_typed!(peek_i16_ne, i16, peek_ne);
+    peek_primitive_typed!(peek_i16_le, i16, peek_le);
+    peek_primitive_typed!(peek_i16_be, i16, peek_be);
+
+    peek_primitive_typed!(peek_i32_ne, i32, peek_ne);
+    peek_primitive_typed!(peek_i32_le, i32, peek_le);
+    peek_primitive_typed!(peek_i32_be, i32, peek_be);
+
+    peek_primitive_typed!(peek_i64_ne, i64, peek_ne);
+    peek_primitive_typed!(peek_i64_le, i64, peek_le);
+    peek_primitive_typed!(peek_i64_be, i64, peek_be);
+
+    peek_primitive_typed!(peek_i128_ne, i128, peek_ne);
+    peek_primitive_typed!(peek_i128_le, i128, peek_le);
+    peek_primitive_typed!(peek_i128_be, i128, peek_be);
+
+    peek_primitive_typed!(peek_isize_ne, isize, peek_ne);
+    peek_primitive_typed!(peek_isize_le, isize, peek_le);
+    peek_primitive_typed!(peek_isize_be, isize, peek_be);
+
+    peek_primitive_typed!(peek_f32_ne, f32, peek_ne);
+    peek_primitive_typed!(peek_f32_le, f32, peek_le);
+    peek_primitive_typed!(peek_f32_be, f32, peek_be);
+
+    peek_primitive_typed!(peek_f64_ne, f64, peek_ne);
+    peek_primitive_typed!(peek_f64_le, f64, peek_le);
+    peek_primitive_typed!(peek_f64_be, f64, peek_be);
+
+    peek_primitive_typed!(peek_char_ne, char, peek_ne);
+    peek_primitive_typed!(peek_char_le, char, peek_le);
+    peek_primitive_typed!(peek_char_be, char, peek_be);
+
+    /// Peeks a dynamic value without advancing the reader.
+    ///
+    /// It's recommended to use the typed wrappers like `peek_vec` instead of this method for cleaner code.
+    /// The buffer grows as the bytes arrive, so a truncated stream fails before a large buffer is allocated.
+    fn peek_dynamic<T: crate::Dynamic>(&mut self, len: usize) -> Result<T> {
+        let mut buf = vec![0; len.min(CHUNK)];
+        loop {
+            self.peek_exact(&mut buf)
+                .map_err(|e| Error::for_value(e, None, type_name::<T>(), len))?;
+            if buf.len() == len {
+                return T::from_bytes(buf);
+            }
+            // doubling keeps peeking the prefix again cheap
+            buf.resize(len.min(buf.len() * 2), 0);
+        }
+    }
+}
+
+impl<T: Read + Seek> PeekVal for T {
+    fn peek_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let pos_before = self.stream_position()?;
+        let result = self.read_exact(buf);
+        self.seek(SeekPos(pos_before))?;
+        result.map_err(|e| Error::from(e).with_offset(Some(pos_before)))
+    }
+}
+
+/// Wrapper for `Read` that buffers peeked and unread bytes, so any reader can look ahead.
+pub struct Pushback<R: Read> {
+    inner: R,
+    buf: VecDeque<u8>,
+}
+
+impl<R: Read> Pushback<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: VecDeque::new(),
+        }
+    }
+
+    /// Pushes bytes back to the reader, so they are returned by the next reads in the given order.
+    pub fn unread(&mut self, bytes: &[u8]) {
+        for byte in bytes.iter().rev() {
+            self.buf.push_front(*byte);
+        }
+    }
+
+    /// Returns the bytes that were peeked or unread but not consumed yet.
+    pub fn buffer(&self) -> &VecDeque<u8> {
+        &self.buf
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader, dropping all buffered bytes.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Pushback<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.buf.is_empty() {
+            return self.inner.read(buf);
+        }
+
+        let len = buf.len().min(self.buf.len());
+        for (target, byte) in buf.iter_mut().zip(self.buf.drain(..len)) {
+            *target = byte;
+        }
+        Ok(len)
+    }
+}
+
+impl<R: Read> PeekVal for Pushback<R> {
+    fn peek_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let mut chunk = [0; 4096];
+        while self.buf.len() < buf.len() {
+            let len = chunk.len().min(buf.len() - self.buf.len());
+            match self.inner.read(&mut chunk[..len]) {
+                Ok(0) => return Err(std::io::Error::from(UnexpectedEof).into()),
+                Ok(read) => self.buf.extend(&chunk[..read]),
+                Err(e) if e.kind() == Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        for (target, byte) in buf.iter_mut().zip(&self.buf) {
+            *target = *byte;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    Error, Result,
+    tagged::{ByteOrder, F32, F64, I16, I32, I64, I128, U16, U32, U64, U128},
+};
+use std::any::type_name;
+use std::mem::{align_of, size_of};
+use std::slice;
+
+/// Marker trait for plain-old-data types that can be reinterpreted from raw bytes.
+///
+/// Only types whose byte representation doesn't depend on the platform are `Pod`,
+/// that's why the native integers are not, use the endian-tagged types like `U32<Le>` instead.
+///
+/// # Safety
+///
+/// Implementors must be valid for any bit pattern and must not contain padding bytes.
+/// Use the `pod!` macro to implement this trait for structs, which checks both at compile time.
+pub unsafe trait Pod: Copy + 'static {}
+
+unsafe impl Pod for () {}
+unsafe impl Pod for u8 {}
+unsafe impl Pod for i8 {}
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+unsafe impl<O: ByteOrder> Pod for U16<O> {}
+unsafe impl<O: ByteOrder> Pod for U32<O> {}
+unsafe impl<O: ByteOrder> Pod for U64<O> {}
+unsafe impl<O: ByteOrder> Pod for U128<O> {}
+unsafe impl<O: ByteOrder> Pod for I16<O> {}
+unsafe impl<O: ByteOrder> Pod for I32<O> {}
+unsafe impl<O: ByteOrder> Pod for I64<O> {}
+unsafe impl<O: ByteOrder> Pod for I128<O> {}
+unsafe impl<O: ByteOrder> Pod for F32<O> {}
+unsafe impl<O: ByteOrder> Pod for F64<O> {}
+
+/// Defines a `repr(C)` struct and implements `Pod` for it.
+///
+/// Fails to compile if a field is not `Pod` or if the struct contains padding.
+/// The struct must derive (or implement) `Clone` and `Copy`.
+///
+/// ```
+/// use dh::{Be, Le, U16, U32, pod};
+///
+/// pod! {
+///     #[derive(Debug, Clone, Copy)]
+///     pub struct Record {
+///         pub id: U32<Le>,
+///         pub kind: U16<Be>,
+///         pub flags: [u8; 2],
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! pod {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $type:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $type),*
+        }
+
+        // SAFETY: all fields are `Pod` and the size check below rules out padding
+        unsafe impl $crate::Pod for $name {}
+
+        const _: () = {
+            const fn assert_pod<T: $crate::Pod>() {}
+            $(assert_pod::<$type>();)*
+            assert!(
+                ::core::mem::size_of::<$name>() == 0 $(+ ::core::mem::size_of::<$type>())*,
+                concat!("`", stringify!($name), "` contains padding")
+            );
+        };
+    };
+}
+
+/// Returns a value with all bytes set to zero.
+pub fn zeroed<T: Pod>() -> T {
+    // SAFETY: `Pod` types are valid for any bit pattern
+    unsafe { std::mem::zeroed() }
+}
+
+/// Returns the bytes of a value.
+pub fn bytes_of<T: Pod>(value: &T) -> &[u8] {
+    slice_bytes(slice::from_ref(value))
+}
+
+/// Returns the mutable bytes of a value.
+pub fn bytes_of_mut<T: Pod>(value: &mut T) -> &mut [u8] {
+    slice_bytes_mut(slice::from_mut(value))
+}
+
+/// Returns the bytes of a slice of values.
+pub fn slice_bytes<T: Pod>(values: &[T]) -> &[u8] {
+    // SAFETY: `Pod` types have no padding, so all bytes are initialized
+    unsafe { slice::from_raw_parts(values.as_ptr().cast(), size_of_val(values)) }
+}
+
+/// Returns the mutable bytes of a slice of values.
+pub fn slice_bytes_mut<T: Pod>(values: &mut [T]) -> &mut [u8] {
+    // SAFETY: `Pod` types have no padding and are valid for any bit pattern
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr().cast(), size_of_val(values)) }
+}
+
+fn check_cast<T: Pod>(bytes: &[u8], exact: bool) -> Result<usize> {
+    let size = size_of::<T>();
+    if (exact && bytes.len() != size)
+        || (!exact && (size == 0 || !bytes.len().is_multiple_of(size)))
+    {
+        return Err(Error::SizeMismatch {
+            offset: None,
+            type_name: type_name::<T>(),
+            expected: size,
+            actual: bytes.len(),
+        });
+    }
+    if bytes.as_ptr().align_offset(align_of::<T>()) != 0 {
+        return Err(Error::Misaligned {
+            offset: None,
+            type_name: type_name::<T>(),
+            align: align_of::<T>(),
+        });
+    }
+    Ok(if exact { 1 } else { bytes.len() / size })
+}
+
+/// Borrows bytes as a value, checking the length and the alignment.
+pub fn from_bytes<T: Pod>(bytes: &[u8]) -> Result<&T> {
+    check_cast::<T>(bytes, true)?;
+    // SAFETY: size and alignment are checked, `Pod` types are valid for any bit pattern
+    Ok(unsafe { &*bytes.as_ptr().cast() })
+}
+
+/// Mutably borrows bytes as a value, checking the length and the alignment.
+pub fn from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut T> {
+    check_cast::<T>(bytes, true)?;
+    // SAFETY: size and alignment are checked, `Pod` types are valid for any bit pattern
+    Ok(unsafe { &mut *bytes.as_mut_ptr().cast() })
+}
+
+/// Borrows bytes as a slice of values, checking the length and the alignment.
+pub fn slice_from_bytes<T: Pod>(bytes: &[u8]) -> Result<&[T]> {
+    let len = check_cast::<T>(bytes, false)?;
+    // SAFETY: size and alignment are checked, `Pod` types are valid for any bit pattern
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr().cast(), len) })
+}
+
+/// Mutably borrows bytes as a slice of values, checking the length and the alignment.
+pub fn slice_from_bytes_mut<T: Pod>(bytes: &mut [u8]) -> Result<&mut [T]> {
+    let len = check_cast::<T>(bytes, false)?;
+    // SAFETY: size and alignment are checked, `Pod` types are valid for any bit pattern
+    Ok(unsafe { slice::from_raw_parts_mut(bytes.as_mut_ptr().cast(), len) })
+}
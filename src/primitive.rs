@@ -1,10 +1,16 @@
+use crate::{Error, Result};
+use std::any::type_name;
+use std::io::{Read, Write};
 use std::mem::size_of;
+use std::num::NonZero;
 
 // marker trait
-pub trait Primitive<T, const S: usize> {
-    fn from_ne_bytes(bytes: [u8; S]) -> Self;
-    fn from_le_bytes(bytes: [u8; S]) -> Self;
-    fn from_be_bytes(bytes: [u8; S]) -> Self;
+//
+// converting from bytes can fail for types that don't accept every bit pattern, like `char`
+pub trait Primitive<T, const S: usize>: Sized {
+    fn from_ne_bytes(bytes: [u8; S]) -> Result<Self>;
+    fn from_le_bytes(bytes: [u8; S]) -> Result<Self>;
+    fn from_be_bytes(bytes: [u8; S]) -> Result<Self>;
 
     fn to_ne_bytes(self) -> [u8; S];
     fn to_le_bytes(self) -> [u8; S];
@@ -17,16 +23,16 @@ macro_rules! impl_primitive {
         const $type_upper: usize = size_of::<$type>();
 
         impl Primitive<$type, $type_upper> for $type {
-            fn from_ne_bytes(bytes: [u8; $type_upper]) -> $type {
-                <$type>::from_ne_bytes(bytes)
+            fn from_ne_bytes(bytes: [u8; $type_upper]) -> Result<$type> {
+                Ok(<$type>::from_ne_bytes(bytes))
             }
 
-            fn from_le_bytes(bytes: [u8; $type_upper]) -> $type {
-                <$type>::from_le_bytes(bytes)
+            fn from_le_bytes(bytes: [u8; $type_upper]) -> Result<$type> {
+                Ok(<$type>::from_le_bytes(bytes))
             }
 
-            fn from_be_bytes(bytes: [u8; $type_upper]) -> $type {
-                <$type>::from_be_bytes(bytes)
+            fn from_be_bytes(bytes: [u8; $type_upper]) -> Result<$type> {
+                Ok(<$type>::from_be_bytes(bytes))
             }
 
             fn to_ne_bytes(self) -> [u8; $type_upper] {
@@ -64,18 +70,18 @@ impl_primitive!(isize, ISIZE);
 impl_primitive!(f32, F32);
 impl_primitive!(f64, F64);
 
-// other primitives (char is not handled as a primitive in dh!)
+// other primitives
 impl<const S: usize> Primitive<[u8; S], S> for [u8; S] {
-    fn from_ne_bytes(bytes: [u8; S]) -> [u8; S] {
-        bytes
+    fn from_ne_bytes(bytes: [u8; S]) -> Result<[u8; S]> {
+        Ok(bytes)
     }
 
-    fn from_le_bytes(bytes: [u8; S]) -> [u8; S] {
-        bytes
+    fn from_le_bytes(bytes: [u8; S]) -> Result<[u8; S]> {
+        Ok(bytes)
     }
 
-    fn from_be_bytes(bytes: [u8; S]) -> [u8; S] {
-        bytes
+    fn from_be_bytes(bytes: [u8; S]) -> Result<[u8; S]> {
+        Ok(bytes)
     }
 
     fn to_ne_bytes(self) -> [u8; S] {
@@ -91,16 +97,16 @@ impl<const S: usize> Primitive<[u8; S], S> for [u8; S] {
     }
 }
 impl Primitive<bool, 1> for bool {
-    fn from_ne_bytes(bytes: [u8; 1]) -> bool {
-        bytes[0] & 1 == 1
+    fn from_ne_bytes(bytes: [u8; 1]) -> Result<bool> {
+        Ok(bytes[0] & 1 == 1)
     }
 
-    fn from_le_bytes(bytes: [u8; 1]) -> bool {
-        bytes[0] & 1 == 1
+    fn from_le_bytes(bytes: [u8; 1]) -> Result<bool> {
+        Ok(bytes[0] & 1 == 1)
     }
 
-    fn from_be_bytes(bytes: [u8; 1]) -> bool {
-        bytes[0] & 1 == 1
+    fn from_be_bytes(bytes: [u8; 1]) -> Result<bool> {
+        Ok(bytes[0] & 1 == 1)
     }
 
     fn to_ne_bytes(self) -> [u8; 1] {
@@ -116,9 +122,15 @@ impl Primitive<bool, 1> for bool {
     }
 }
 impl Primitive<(), 0> for () {
-    fn from_ne_bytes(_: [u8; 0]) {}
-    fn from_le_bytes(_: [u8; 0]) {}
-    fn from_be_bytes(_: [u8; 0]) {}
+    fn from_ne_bytes(_: [u8; 0]) -> Result<()> {
+        Ok(())
+    }
+    fn from_le_bytes(_: [u8; 0]) -> Result<()> {
+        Ok(())
+    }
+    fn from_be_bytes(_: [u8; 0]) -> Result<()> {
+        Ok(())
+    }
 
     fn to_ne_bytes(self) -> [u8; 0] {
         []
@@ -130,3 +142,129 @@ impl Primitive<(), 0> for () {
         []
     }
 }
+
+fn invalid_char() -> Error {
+    Error::InvalidEncoding {
+        offset: None,
+        type_name: "char",
+        reason: "invalid Unicode code point",
+    }
+}
+
+// chars are stored as UTF-32 code points
+impl Primitive<char, 4> for char {
+    fn from_ne_bytes(bytes: [u8; 4]) -> Result<char> {
+        char::from_u32(u32::from_ne_bytes(bytes)).ok_or_else(invalid_char)
+    }
+
+    fn from_le_bytes(bytes: [u8; 4]) -> Result<char> {
+        char::from_u32(u32::from_le_bytes(bytes)).ok_or_else(invalid_char)
+    }
+
+    fn from_be_bytes(bytes: [u8; 4]) -> Result<char> {
+        char::from_u32(u32::from_be_bytes(bytes)).ok_or_else(invalid_char)
+    }
+
+    fn to_ne_bytes(self) -> [u8; 4] {
+        (self as u32).to_ne_bytes()
+    }
+
+    fn to_le_bytes(self) -> [u8; 4] {
+        (self as u32).to_le_bytes()
+    }
+
+    fn to_be_bytes(self) -> [u8; 4] {
+        (self as u32).to_be_bytes()
+    }
+}
+
+// non-zero integers reject zero when reading
+macro_rules! impl_primitive_non_zero {
+    ($type:ty, $type_upper:ident) => {
+        impl Primitive<NonZero<$type>, $type_upper> for NonZero<$type> {
+            fn from_ne_bytes(bytes: [u8; $type_upper]) -> Result<Self> {
+                Self::new(<$type>::from_ne_bytes(bytes)).ok_or_else(zero::<Self>)
+            }
+
+            fn from_le_bytes(bytes: [u8; $type_upper]) -> Result<Self> {
+                Self::new(<$type>::from_le_bytes(bytes)).ok_or_else(zero::<Self>)
+            }
+
+            fn from_be_bytes(bytes: [u8; $type_upper]) -> Result<Self> {
+                Self::new(<$type>::from_be_bytes(bytes)).ok_or_else(zero::<Self>)
+            }
+
+            fn to_ne_bytes(self) -> [u8; $type_upper] {
+                self.get().to_ne_bytes()
+            }
+
+            fn to_le_bytes(self) -> [u8; $type_upper] {
+                self.get().to_le_bytes()
+            }
+
+            fn to_be_bytes(self) -> [u8; $type_upper] {
+                self.get().to_be_bytes()
+            }
+        }
+    };
+}
+
+fn zero<T>() -> Error {
+    Error::InvalidEncoding {
+        offset: None,
+        type_name: type_name::<T>(),
+        reason: "value is zero",
+    }
+}
+
+impl_primitive_non_zero!(u8, U8);
+impl_primitive_non_zero!(u16, U16);
+impl_primitive_non_zero!(u32, U32);
+impl_primitive_non_zero!(u64, U64);
+impl_primitive_non_zero!(u128, U128);
+impl_primitive_non_zero!(usize, USIZE);
+
+impl_primitive_non_zero!(i8, I8);
+impl_primitive_non_zero!(i16, I16);
+impl_primitive_non_zero!(i32, I32);
+impl_primitive_non_zero!(i64, I64);
+impl_primitive_non_zero!(i128, I128);
+impl_primitive_non_zero!(isize, ISIZE);
+
+/// Reads a char encoded as a UTF-8 sequence of 1 to 4 bytes.
+pub(crate) fn read_utf8_char<R: Read + ?Sized>(reader: &mut R) -> Result<char> {
+    let mut buf = [0; 4];
+    reader
+        .read_exact(&mut buf[..1])
+        .map_err(|e| Error::for_value(e, None, "char", 1))?;
+
+    let len = match buf[0] {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => {
+            return Err(Error::InvalidUtf8 {
+                offset: None,
+                len: 1,
+            });
+        }
+    };
+    reader
+        .read_exact(&mut buf[1..len])
+        .map_err(|e| Error::for_value(e, None, "char", len))?;
+
+    match std::str::from_utf8(&buf[..len]) {
+        Ok(str) => Ok(str.chars().next().unwrap()),
+        Err(_) => Err(Error::InvalidUtf8 { offset: None, len }),
+    }
+}
+
+/// Writes a char as a UTF-8 sequence of 1 to 4 bytes.
+pub(crate) fn write_utf8_char<W: Write + ?Sized>(writer: &mut W, data: char) -> Result<()> {
+    let mut buf = [0; 4];
+    let bytes = data.encode_utf8(&mut buf).as_bytes();
+    writer
+        .write_all(bytes)
+        .map_err(|e| Error::for_value(e, None, "char", bytes.len()))
+}
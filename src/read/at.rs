@@ -1,5 +1,13 @@
-use crate::{Endianess, Primitive, Result};
-use std::io::{Read, Seek, SeekFrom::Start as SeekPos, Write};
+use crate::{
+    BoolMode, Endianess, Error, F2Dot14, Fixed, Fixed16_16, FixedBits, Flags, HexDump, Pattern,
+    Primitive, Result,
+    checksum::{self, Algorithm, Checksum},
+    decimal, flags, float,
+    limit::read_bytes,
+    odd, primitive, search,
+};
+use std::any::type_name;
+use std::io::{Read, Seek, SeekFrom, SeekFrom::Start as SeekPos, Write};
 
 macro_rules! read_primitive {
     ($fn_name:ident, $read_fn_name:ident) => {
@@ -10,9 +18,12 @@ macro_rules! read_primitive {
             let mut buf = [0; S];
             let pos_before = self.stream_position()?;
             self.seek(SeekPos(pos as u64))?;
-            self.read_exact(&mut buf)?;
+            let response = self
+                .read_exact(&mut buf)
+                .map_err(|e| Error::for_value(e, Some(pos as u64), type_name::<T>(), S));
             self.seek(SeekPos(pos_before))?;
-            Ok(T::$read_fn_name(buf))
+            response?;
+            T::$read_fn_name(buf).map_err(|e| e.with_offset(Some(pos as u64)))
         }
     };
 }
@@ -51,7 +62,92 @@ macro_rules! read_dynamic_typed {
         fn $fn_name(&mut self, pos: usize, len: usize) -> Result<$return_type> {
             let pos_before = self.stream_position()?;
             self.seek(SeekPos(pos as u64))?;
-            let result = self.read_dynamic_at(len);
+            let result = self
+                .read_dynamic_at(len)
+                .map_err(|e| e.with_offset(Some(pos as u64)));
+            self.seek(SeekPos(pos_before))?;
+            result
+        }
+    };
+}
+
+macro_rules! read_odd_typed {
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $bytes:literal) => {
+        /// Typed wrapper around `read_uint_n_at` or `read_int_n_at`.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess) -> Result<$return_type> {
+            Ok(self.$read_fn(pos, $bytes, endianess)? as $return_type)
+        }
+    };
+
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $bytes:literal, $endianess:ident) => {
+        /// Typed wrapper around `read_uint_n_at` or `read_int_n_at`.
+        fn $fn_name(&mut self, pos: usize) -> Result<$return_type> {
+            Ok(self.$read_fn(pos, $bytes, Endianess::$endianess)? as $return_type)
+        }
+    };
+}
+
+macro_rules! read_odd {
+    ($fn_name:ident, $return_type:ty, $read_fn:ident) => {
+        /// Reads an integer with a width of 1 to 8 bytes at a specific position.
+        ///
+        /// It's recommended to use the typed wrappers like `read_u24_le_at` instead of this method for cleaner code.
+        fn $fn_name(
+            &mut self,
+            pos: usize,
+            bytes: usize,
+            endianess: Endianess,
+        ) -> Result<$return_type> {
+            let pos_before = self.stream_position()?;
+            self.seek(SeekPos(pos as u64))?;
+            let result =
+                odd::$read_fn(self, bytes, endianess).map_err(|e| e.with_offset(Some(pos as u64)));
+            self.seek(SeekPos(pos_before))?;
+            result
+        }
+    };
+}
+
+macro_rules! read_float_typed {
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $convert:path) => {
+        /// Reads a floating point value stored in another format at a specific position and converts it.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess) -> Result<$return_type> {
+            Ok($convert(self.$read_fn(pos, endianess)?))
+        }
+    };
+
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $convert:path, $endianess:ident) => {
+        /// Reads a floating point value stored in another format at a specific position and converts it.
+        fn $fn_name(&mut self, pos: usize) -> Result<$return_type> {
+            Ok($convert(self.$read_fn(pos, Endianess::$endianess)?))
+        }
+    };
+}
+
+macro_rules! read_float_bytes {
+    ($fn_name:ident, $convert:path) => {
+        /// Reads a floating point value stored in another format at a specific position and converts it.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess) -> Result<f64> {
+            Ok($convert(self.read_u8_array_at(pos)?, endianess))
+        }
+    };
+
+    ($fn_name:ident, $convert:path, $endianess:ident) => {
+        /// Reads a floating point value stored in another format at a specific position and converts it.
+        fn $fn_name(&mut self, pos: usize) -> Result<f64> {
+            Ok($convert(self.read_u8_array_at(pos)?, Endianess::$endianess))
+        }
+    };
+}
+
+macro_rules! read_decimal {
+    ($(#[$attr:meta])* $fn_name:ident, $return_type:ty, $read_fn:ident) => {
+        $(#[$attr])*
+        fn $fn_name(&mut self, pos: usize, len: usize) -> Result<$return_type> {
+            let pos_before = self.stream_position()?;
+            self.seek(SeekPos(pos as u64))?;
+            let result =
+                decimal::$read_fn(self, len).map_err(|e| e.with_offset(Some(pos as u64)));
             self.seek(SeekPos(pos_before))?;
             result
         }
@@ -85,6 +181,84 @@ pub trait ReadValAt: Read + Seek {
 
     read_primitive_typed!(read_bool_at, bool, read_ne_at);
 
+    /// Reads a 1 byte bool at a specific position, decoded as specified by `mode`.
+    fn read_bool_mode_at(&mut self, pos: usize, mode: BoolMode) -> Result<bool> {
+        mode.decode(self.read_u8_at(pos)? as u64)
+            .map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a 2 byte bool at a specific position, decoded as specified by `mode`.
+    fn read_bool16_at(&mut self, pos: usize, endianess: Endianess, mode: BoolMode) -> Result<bool> {
+        mode.decode(self.read_u16_at(pos, endianess)? as u64)
+            .map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a 4 byte bool like the Windows `BOOL` at a specific position, decoded as specified by `mode`.
+    fn read_bool32_at(&mut self, pos: usize, endianess: Endianess, mode: BoolMode) -> Result<bool> {
+        mode.decode(self.read_u32_at(pos, endianess)? as u64)
+            .map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    read_primitive_typed!(read_char_at, char);
+
+    /// Reads a char encoded as UTF-8 at a specific position, which takes 1 to 4 bytes.
+    fn read_char_utf8_at(&mut self, pos: usize) -> Result<char> {
+        let pos_before = self.stream_position()?;
+        self.seek(SeekPos(pos as u64))?;
+        let result = primitive::read_utf8_char(self).map_err(|e| e.with_offset(Some(pos as u64)));
+        self.seek(SeekPos(pos_before))?;
+        result
+    }
+
+    /// Reads an integer of the type `T` at a specific position and converts it to the enum `E` with `TryFrom`.
+    ///
+    /// Fails with `Error::UnknownDiscriminant` if the conversion fails.
+    fn read_enum_at<E, T, const S: usize>(&mut self, pos: usize, endianess: Endianess) -> Result<E>
+    where
+        E: TryFrom<T>,
+        T: Primitive<T, S> + Copy + Into<i128>,
+    {
+        let value = match endianess {
+            Endianess::Little => self.read_le_at::<T, T, S>(pos)?,
+            Endianess::Big => self.read_be_at::<T, T, S>(pos)?,
+            Endianess::Native => self.read_ne_at::<T, T, S>(pos)?,
+        };
+        flags::to_enum(value).map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a set of bit flags at a specific position,
+    /// failing with `Error::UnknownFlags` if bits are set that are not defined.
+    fn read_flags_at<F, const S: usize>(&mut self, pos: usize, endianess: Endianess) -> Result<F>
+    where
+        F: Flags,
+        F::Bits: Primitive<F::Bits, S>,
+    {
+        let bits = match endianess {
+            Endianess::Little => self.read_le_at::<F::Bits, F::Bits, S>(pos)?,
+            Endianess::Big => self.read_be_at::<F::Bits, F::Bits, S>(pos)?,
+            Endianess::Native => self.read_ne_at::<F::Bits, F::Bits, S>(pos)?,
+        };
+        flags::from_bits(bits).map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a set of bit flags at a specific position, keeping bits that are not defined.
+    fn read_flags_retain_at<F, const S: usize>(
+        &mut self,
+        pos: usize,
+        endianess: Endianess,
+    ) -> Result<F>
+    where
+        F: Flags,
+        F::Bits: Primitive<F::Bits, S>,
+    {
+        let bits = match endianess {
+            Endianess::Little => self.read_le_at::<F::Bits, F::Bits, S>(pos)?,
+            Endianess::Big => self.read_be_at::<F::Bits, F::Bits, S>(pos)?,
+            Endianess::Native => self.read_ne_at::<F::Bits, F::Bits, S>(pos)?,
+        };
+        Ok(F::from_bits_retain(bits))
+    }
+
     read_dynamic_typed!(read_vec_at, Vec<u8>);
     read_dynamic_typed!(read_str_at, String);
 
@@ -140,12 +314,205 @@ pub trait ReadValAt: Read + Seek {
     read_primitive_typed!(read_f64_le_at, f64, read_le_at);
     read_primitive_typed!(read_f64_be_at, f64, read_be_at);
 
+    read_primitive_typed!(read_char_ne_at, char, read_ne_at);
+    read_primitive_typed!(read_char_le_at, char, read_le_at);
+    read_primitive_typed!(read_char_be_at, char, read_be_at);
+
+    read_odd!(read_uint_n_at, u64, read_uint);
+    read_odd!(read_int_n_at, i64, read_int);
+
+    read_odd_typed!(read_u24_at, u32, read_uint_n_at, 3);
+    read_odd_typed!(read_u24_ne_at, u32, read_uint_n_at, 3, Native);
+    read_odd_typed!(read_u24_le_at, u32, read_uint_n_at, 3, Little);
+    read_odd_typed!(read_u24_be_at, u32, read_uint_n_at, 3, Big);
+
+    read_odd_typed!(read_i24_at, i32, read_int_n_at, 3);
+    read_odd_typed!(read_i24_ne_at, i32, read_int_n_at, 3, Native);
+    read_odd_typed!(read_i24_le_at, i32, read_int_n_at, 3, Little);
+    read_odd_typed!(read_i24_be_at, i32, read_int_n_at, 3, Big);
+
+    read_odd_typed!(read_u40_at, u64, read_uint_n_at, 5);
+    read_odd_typed!(read_u40_ne_at, u64, read_uint_n_at, 5, Native);
+    read_odd_typed!(read_u40_le_at, u64, read_uint_n_at, 5, Little);
+    read_odd_typed!(read_u40_be_at, u64, read_uint_n_at, 5, Big);
+
+    read_odd_typed!(read_i40_at, i64, read_int_n_at, 5);
+    read_odd_typed!(read_i40_ne_at, i64, read_int_n_at, 5, Native);
+    read_odd_typed!(read_i40_le_at, i64, read_int_n_at, 5, Little);
+    read_odd_typed!(read_i40_be_at, i64, read_int_n_at, 5, Big);
+
+    read_odd_typed!(read_u48_at, u64, read_uint_n_at, 6);
+    read_odd_typed!(read_u48_ne_at, u64, read_uint_n_at, 6, Native);
+    read_odd_typed!(read_u48_le_at, u64, read_uint_n_at, 6, Little);
+    read_odd_typed!(read_u48_be_at, u64, read_uint_n_at, 6, Big);
+
+    read_odd_typed!(read_i48_at, i64, read_int_n_at, 6);
+    read_odd_typed!(read_i48_ne_at, i64, read_int_n_at, 6, Native);
+    read_odd_typed!(read_i48_le_at, i64, read_int_n_at, 6, Little);
+    read_odd_typed!(read_i48_be_at, i64, read_int_n_at, 6, Big);
+
+    read_odd_typed!(read_u56_at, u64, read_uint_n_at, 7);
+    read_odd_typed!(read_u56_ne_at, u64, read_uint_n_at, 7, Native);
+    read_odd_typed!(read_u56_le_at, u64, read_uint_n_at, 7, Little);
+    read_odd_typed!(read_u56_be_at, u64, read_uint_n_at, 7, Big);
+
+    read_odd_typed!(read_i56_at, i64, read_int_n_at, 7);
+    read_odd_typed!(read_i56_ne_at, i64, read_int_n_at, 7, Native);
+    read_odd_typed!(read_i56_le_at, i64, read_int_n_at, 7, Little);
+    read_odd_typed!(read_i56_be_at, i64, read_int_n_at, 7, Big);
+
+    read_float_typed!(read_f16_at, f32, read_u16_at, float::f16_to_f32);
+    read_float_typed!(read_f16_ne_at, f32, read_u16_at, float::f16_to_f32, Native);
+    read_float_typed!(read_f16_le_at, f32, read_u16_at, float::f16_to_f32, Little);
+    read_float_typed!(read_f16_be_at, f32, read_u16_at, float::f16_to_f32, Big);
+
+    read_float_typed!(read_bf16_at, f32, read_u16_at, float::bf16_to_f32);
+    read_float_typed!(
+        read_bf16_ne_at,
+        f32,
+        read_u16_at,
+        float::bf16_to_f32,
+        Native
+    );
+    read_float_typed!(
+        read_bf16_le_at,
+        f32,
+        read_u16_at,
+        float::bf16_to_f32,
+        Little
+    );
+    read_float_typed!(read_bf16_be_at, f32, read_u16_at, float::bf16_to_f32, Big);
+
+    read_float_bytes!(read_f80_at, float::f80_from_bytes);
+    read_float_bytes!(read_f80_ne_at, float::f80_from_bytes, Native);
+    read_float_bytes!(read_f80_le_at, float::f80_from_bytes, Little);
+    read_float_bytes!(read_f80_be_at, float::f80_from_bytes, Big);
+
+    read_float_typed!(read_ibm32_at, f64, read_u32_at, float::ibm32_to_f64);
+    read_float_typed!(
+        read_ibm32_ne_at,
+        f64,
+        read_u32_at,
+        float::ibm32_to_f64,
+        Native
+    );
+    read_float_typed!(
+        read_ibm32_le_at,
+        f64,
+        read_u32_at,
+        float::ibm32_to_f64,
+        Little
+    );
+    read_float_typed!(read_ibm32_be_at, f64, read_u32_at, float::ibm32_to_f64, Big);
+
+    read_float_typed!(read_ibm64_at, f64, read_u64_at, float::ibm64_to_f64);
+    read_float_typed!(
+        read_ibm64_ne_at,
+        f64,
+        read_u64_at,
+        float::ibm64_to_f64,
+        Native
+    );
+    read_float_typed!(
+        read_ibm64_le_at,
+        f64,
+        read_u64_at,
+        float::ibm64_to_f64,
+        Little
+    );
+    read_float_typed!(read_ibm64_be_at, f64, read_u64_at, float::ibm64_to_f64, Big);
+
+    /// Reads a VAX F_floating value at a specific position and converts it to `f64`.
+    fn read_vax_f_at(&mut self, pos: usize) -> Result<f64> {
+        float::vax_f_to_f64(self.read_u8_array_at(pos)?)
+            .map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a VAX D_floating value at a specific position and converts it to `f64`.
+    fn read_vax_d_at(&mut self, pos: usize) -> Result<f64> {
+        float::vax_d_to_f64(self.read_u8_array_at(pos)?)
+            .map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a VAX G_floating value at a specific position and converts it to `f64`.
+    fn read_vax_g_at(&mut self, pos: usize) -> Result<f64> {
+        float::vax_g_to_f64(self.read_u8_array_at(pos)?)
+            .map_err(|e| e.with_offset(Some(pos as u64)))
+    }
+
+    /// Reads a fixed-point number with the integer type `T` and `FRAC` fractional bits at a specific position.
+    ///
+    /// It's recommended to use the typed wrappers like `read_fixed16_16_at` for common formats.
+    fn read_fixed_at<T, const FRAC: u32, const S: usize>(
+        &mut self,
+        pos: usize,
+        endianess: Endianess,
+    ) -> Result<Fixed<T, FRAC>>
+    where
+        T: FixedBits + Primitive<T, S>,
+    {
+        use Endianess::*;
+        match endianess {
+            Little => self.read_le_at(pos),
+            Big => self.read_be_at(pos),
+            Native => self.read_ne_at(pos),
+        }
+    }
+
+    read_primitive_typed!(read_fixed16_16_at, Fixed16_16);
+    read_primitive_typed!(read_fixed16_16_ne_at, Fixed16_16, read_ne_at);
+    read_primitive_typed!(read_fixed16_16_le_at, Fixed16_16, read_le_at);
+    read_primitive_typed!(read_fixed16_16_be_at, Fixed16_16, read_be_at);
+
+    read_primitive_typed!(read_f2dot14_at, F2Dot14);
+    read_primitive_typed!(read_f2dot14_ne_at, F2Dot14, read_ne_at);
+    read_primitive_typed!(read_f2dot14_le_at, F2Dot14, read_le_at);
+    read_primitive_typed!(read_f2dot14_be_at, F2Dot14, read_be_at);
+
+    read_decimal!(
+        /// Reads a binary-coded decimal field of `len` bytes at a specific position.
+        ///
+        /// Two digits are stored per byte with the high nibble first.
+        /// Fails for nibbles above 9 and for fields longer than 19 bytes, use `read_bcd_str_at` for those.
+        read_bcd_at,
+        u128,
+        read_bcd
+    );
+
+    read_decimal!(
+        /// Reads a binary-coded decimal field of `len` bytes at a specific position as a decimal string.
+        ///
+        /// Leading zeros are removed.
+        read_bcd_str_at,
+        String,
+        read_bcd_str
+    );
+
+    read_decimal!(
+        /// Reads an IBM packed decimal (COMP-3) field of `len` bytes at a specific position.
+        ///
+        /// The sign is stored in the last nibble, `C`, `A`, `E` and `F` are positive, `D` and `B` are negative.
+        /// Fails for invalid nibbles and for fields longer than 19 bytes, use `read_packed_decimal_str_at` for those.
+        read_packed_decimal_at,
+        i128,
+        read_packed
+    );
+
+    read_decimal!(
+        /// Reads an IBM packed decimal (COMP-3) field of `len` bytes at a specific position as a decimal string.
+        read_packed_decimal_str_at,
+        String,
+        read_packed_str
+    );
+
     /// Reads a dynamic value from the reader.
     ///
     /// It's recommended to use the typed wrappers like `read_vec_at` instead of this method for cleaner code.
+    ///
+    /// The buffer grows while reading, so a truncated stream fails before `len` bytes are allocated.
     fn read_dynamic_at<T: crate::Dynamic>(&mut self, len: usize) -> Result<T> {
-        let mut buf = vec![0; len];
-        self.read_exact(&mut buf)?;
+        let buf =
+            read_bytes(self, len).map_err(|e| Error::for_value(e, None, type_name::<T>(), len))?;
         T::from_bytes(buf)
     }
 
@@ -175,6 +542,118 @@ pub trait ReadValAt: Read + Seek {
         self.seek(SeekPos(pos_before))?;
         Ok(())
     }
+
+    /// Runs sequential reads and records the position where they started in their error.
+    ///
+    /// Errors that already know their offset keep it, e.g. from `*_at` methods or nested calls.
+    fn read_positioned<T>(&mut self, read: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let pos = self.stream_position()?;
+        read(self).map_err(|e| e.with_offset(Some(pos)))
+    }
+
+    /// Computes the checksum of `len` bytes at a specific position without moving the cursor.
+    ///
+    /// The result is widened to `u64`, see `Algorithm::size` for the actual width.
+    fn checksum_at(&mut self, pos: u64, len: u64, algorithm: Algorithm) -> Result<u64> {
+        let pos_before = self.stream_position()?;
+        self.seek(SeekPos(pos))?;
+        let response = algorithm
+            .compute(self, len)
+            .map_err(|e| e.with_offset(Some(pos)));
+        self.seek(SeekPos(pos_before))?;
+        response
+    }
+
+    /// Returns the position of the first match between the cursor and the end of the stream.
+    ///
+    /// The cursor is not moved.
+    fn find(&mut self, pattern: impl Into<Pattern>) -> Result<Option<u64>> {
+        let pos_before = self.stream_position()?;
+        let end = self.seek(SeekFrom::End(0))?;
+        let response = self.find_at(pos_before, end, pattern);
+        self.seek(SeekPos(pos_before))?;
+        response
+    }
+
+    /// Returns the positions of all non-overlapping matches between the cursor and the end of the stream.
+    ///
+    /// The cursor is not moved.
+    fn find_all(&mut self, pattern: impl Into<Pattern>) -> Result<Vec<u64>> {
+        let pattern = pattern.into();
+        let pos_before = self.stream_position()?;
+        let mut matches = Vec::new();
+        let response = search::scan(self, &pattern, pos_before, u64::MAX, |pos| {
+            matches.push(pos);
+            true
+        });
+        self.seek(SeekPos(pos_before))?;
+        response.map(|_| matches)
+    }
+
+    /// Returns the position of the last match between the cursor and the end of the stream,
+    /// searching backwards from the end, e.g. for trailers.
+    ///
+    /// The cursor is not moved.
+    fn rfind(&mut self, pattern: impl Into<Pattern>) -> Result<Option<u64>> {
+        let pattern = pattern.into();
+        let pos_before = self.stream_position()?;
+        let end = self.seek(SeekFrom::End(0))?;
+        let response = search::scan_back(self, &pattern, pos_before, end);
+        self.seek(SeekPos(pos_before))?;
+        response
+    }
+
+    /// Returns the position of the first match that lies completely within `start..end`.
+    ///
+    /// The cursor is not moved.
+    fn find_at(
+        &mut self,
+        start: u64,
+        end: u64,
+        pattern: impl Into<Pattern>,
+    ) -> Result<Option<u64>> {
+        let pattern = pattern.into();
+        let pos_before = self.stream_position()?;
+        let last_start = end
+            .checked_sub(pattern.len() as u64)
+            .map_or(0, |last| last.saturating_add(1));
+        let mut found = None;
+        let response = search::scan(self, &pattern, start, last_start, |pos| {
+            found = Some(pos);
+            false
+        });
+        self.seek(SeekPos(pos_before))?;
+        response.map(|_| found)
+    }
+
+    /// Writes a hexdump of `len` bytes at a specific position without moving the cursor.
+    ///
+    /// The offsets in the dump are the positions in the stream.
+    fn hexdump_at(
+        &mut self,
+        pos: u64,
+        len: u64,
+        dump: &HexDump,
+        target: &mut dyn Write,
+    ) -> Result<()> {
+        let pos_before = self.stream_position()?;
+        self.seek(SeekPos(pos))?;
+        let response = dump.dump_from(&mut self.take(len), pos, target);
+        self.seek(SeekPos(pos_before))?;
+        response.map(|_| ())
+    }
+
+    /// Hashes `len` bytes at a specific position without moving the cursor.
+    ///
+    /// Works with any `Checksum`, e.g. `dh::hash::Sha256`, so the digest can be compared
+    /// with one read by `read_u8_array_at`.
+    fn hash_at<H: Checksum + Default>(&mut self, pos: u64, len: u64) -> Result<H::Output> {
+        let pos_before = self.stream_position()?;
+        self.seek(SeekPos(pos))?;
+        let response = checksum::sum(H::default(), self, len).map_err(|e| e.with_offset(Some(pos)));
+        self.seek(SeekPos(pos_before))?;
+        response
+    }
 }
 
 impl<T: Read + Seek> ReadValAt for T {}
@@ -1,6 +1,14 @@
 #[cfg(feature = "vli")]
 use crate::variable;
-use crate::{Endianess, Primitive, Result};
+use crate::{
+    BoolMode, Endianess, Error, F2Dot14, Fixed, Fixed16_16, FixedBits, Flags, Pod, Primitive,
+    Result,
+    compress::{self, Format},
+    decimal, flags, float,
+    limit::read_bytes,
+    odd, pod, primitive,
+};
+use std::any::type_name;
 use std::io::{Read, Seek, Write};
 
 macro_rules! read_primitive {
@@ -10,8 +18,9 @@ macro_rules! read_primitive {
         /// It's recommended to use the typed wrappers like `read_u8` instead of this method for cleaner code.
         fn $fn_name<T: Primitive<U, S>, U, const S: usize>(&mut self) -> Result<T> {
             let mut buf = [0; S];
-            self.read_exact(&mut buf)?;
-            Ok(T::$read_fn_name(buf))
+            self.read_exact(&mut buf)
+                .map_err(|e| Error::for_value(e, None, type_name::<T>(), S))?;
+            T::$read_fn_name(buf)
         }
     };
 }
@@ -63,6 +72,54 @@ macro_rules! read_variable {
     };
 }
 
+macro_rules! read_odd_typed {
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $bytes:literal) => {
+        /// Typed wrapper around `read_uint_n` or `read_int_n`.
+        fn $fn_name(&mut self, endianess: Endianess) -> Result<$return_type> {
+            Ok(self.$read_fn($bytes, endianess)? as $return_type)
+        }
+    };
+
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $bytes:literal, $endianess:ident) => {
+        /// Typed wrapper around `read_uint_n` or `read_int_n`.
+        fn $fn_name(&mut self) -> Result<$return_type> {
+            Ok(self.$read_fn($bytes, Endianess::$endianess)? as $return_type)
+        }
+    };
+}
+
+macro_rules! read_float_typed {
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $convert:path) => {
+        /// Reads a floating point value stored in another format and converts it.
+        fn $fn_name(&mut self, endianess: Endianess) -> Result<$return_type> {
+            Ok($convert(self.$read_fn(endianess)?))
+        }
+    };
+
+    ($fn_name:ident, $return_type:ty, $read_fn:ident, $convert:path, $endianess:ident) => {
+        /// Reads a floating point value stored in another format and converts it.
+        fn $fn_name(&mut self) -> Result<$return_type> {
+            Ok($convert(self.$read_fn(Endianess::$endianess)?))
+        }
+    };
+}
+
+macro_rules! read_float_bytes {
+    ($fn_name:ident, $convert:path) => {
+        /// Reads a floating point value stored in another format and converts it.
+        fn $fn_name(&mut self, endianess: Endianess) -> Result<f64> {
+            Ok($convert(self.read_u8_array()?, endianess))
+        }
+    };
+
+    ($fn_name:ident, $convert:path, $endianess:ident) => {
+        /// Reads a floating point value stored in another format and converts it.
+        fn $fn_name(&mut self) -> Result<f64> {
+            Ok($convert(self.read_u8_array()?, Endianess::$endianess))
+        }
+    };
+}
+
 /// Extension trait for `Read` that provides methods for reading supported value types.
 ///
 /// **Note:** do not borrow this as `&mut dyn ReadVal`, as this would not compile. Use `&mut dyn Read` instead.
@@ -104,6 +161,72 @@ pub trait ReadVal: Read {
 
     read_primitive_typed!(read_bool, bool, read_ne);
 
+    /// Reads a 1 byte bool, decoded as specified by `mode`.
+    fn read_bool_mode(&mut self, mode: BoolMode) -> Result<bool> {
+        mode.decode(self.read_u8()? as u64)
+    }
+
+    /// Reads a 2 byte bool, decoded as specified by `mode`.
+    fn read_bool16(&mut self, endianess: Endianess, mode: BoolMode) -> Result<bool> {
+        mode.decode(self.read_u16(endianess)? as u64)
+    }
+
+    /// Reads a 4 byte bool like the Windows `BOOL`, decoded as specified by `mode`.
+    fn read_bool32(&mut self, endianess: Endianess, mode: BoolMode) -> Result<bool> {
+        mode.decode(self.read_u32(endianess)? as u64)
+    }
+
+    read_primitive_typed!(read_char, char);
+
+    /// Reads a char encoded as UTF-8, which takes 1 to 4 bytes.
+    fn read_char_utf8(&mut self) -> Result<char> {
+        primitive::read_utf8_char(self)
+    }
+
+    /// Reads an integer of the type `T` and converts it to the enum `E` with `TryFrom`.
+    ///
+    /// Fails with `Error::UnknownDiscriminant` if the conversion fails.
+    fn read_enum<E, T, const S: usize>(&mut self, endianess: Endianess) -> Result<E>
+    where
+        E: TryFrom<T>,
+        T: Primitive<T, S> + Copy + Into<i128>,
+    {
+        let value = match endianess {
+            Endianess::Little => self.read_le::<T, T, S>()?,
+            Endianess::Big => self.read_be::<T, T, S>()?,
+            Endianess::Native => self.read_ne::<T, T, S>()?,
+        };
+        flags::to_enum(value)
+    }
+
+    /// Reads a set of bit flags, failing with `Error::UnknownFlags` if bits are set that are not defined.
+    fn read_flags<F, const S: usize>(&mut self, endianess: Endianess) -> Result<F>
+    where
+        F: Flags,
+        F::Bits: Primitive<F::Bits, S>,
+    {
+        let bits = match endianess {
+            Endianess::Little => self.read_le::<F::Bits, F::Bits, S>()?,
+            Endianess::Big => self.read_be::<F::Bits, F::Bits, S>()?,
+            Endianess::Native => self.read_ne::<F::Bits, F::Bits, S>()?,
+        };
+        flags::from_bits(bits)
+    }
+
+    /// Reads a set of bit flags, keeping bits that are not defined.
+    fn read_flags_retain<F, const S: usize>(&mut self, endianess: Endianess) -> Result<F>
+    where
+        F: Flags,
+        F::Bits: Primitive<F::Bits, S>,
+    {
+        let bits = match endianess {
+            Endianess::Little => self.read_le::<F::Bits, F::Bits, S>()?,
+            Endianess::Big => self.read_be::<F::Bits, F::Bits, S>()?,
+            Endianess::Native => self.read_ne::<F::Bits, F::Bits, S>()?,
+        };
+        Ok(F::from_bits_retain(bits))
+    }
+
     read_dynamic_typed!(read_vec, Vec<u8>);
     read_dynamic_typed!(read_str, String);
 
@@ -159,21 +282,259 @@ pub trait ReadVal: Read {
     read_primitive_typed!(read_f64_le, f64, read_le);
     read_primitive_typed!(read_f64_be, f64, read_be);
 
+    read_primitive_typed!(read_char_ne, char, read_ne);
+    read_primitive_typed!(read_char_le, char, read_le);
+    read_primitive_typed!(read_char_be, char, read_be);
+
+    /// Reads an unsigned integer with a width of 1 to 8 bytes.
+    ///
+    /// It's recommended to use the typed wrappers like `read_u24_le` instead of this method for cleaner code.
+    fn read_uint_n(&mut self, bytes: usize, endianess: Endianess) -> Result<u64> {
+        odd::read_uint(self, bytes, endianess)
+    }
+
+    /// Reads a signed integer with a width of 1 to 8 bytes and extends its sign.
+    ///
+    /// It's recommended to use the typed wrappers like `read_i24_le` instead of this method for cleaner code.
+    fn read_int_n(&mut self, bytes: usize, endianess: Endianess) -> Result<i64> {
+        odd::read_int(self, bytes, endianess)
+    }
+
+    read_odd_typed!(read_u24, u32, read_uint_n, 3);
+    read_odd_typed!(read_u24_ne, u32, read_uint_n, 3, Native);
+    read_odd_typed!(read_u24_le, u32, read_uint_n, 3, Little);
+    read_odd_typed!(read_u24_be, u32, read_uint_n, 3, Big);
+
+    read_odd_typed!(read_i24, i32, read_int_n, 3);
+    read_odd_typed!(read_i24_ne, i32, read_int_n, 3, Native);
+    read_odd_typed!(read_i24_le, i32, read_int_n, 3, Little);
+    read_odd_typed!(read_i24_be, i32, read_int_n, 3, Big);
+
+    read_odd_typed!(read_u40, u64, read_uint_n, 5);
+    read_odd_typed!(read_u40_ne, u64, read_uint_n, 5, Native);
+    read_odd_typed!(read_u40_le, u64, read_uint_n, 5, Little);
+    read_odd_typed!(read_u40_be, u64, read_uint_n, 5, Big);
+
+    read_odd_typed!(read_i40, i64, read_int_n, 5);
+    read_odd_typed!(read_i40_ne, i64, read_int_n, 5, Native);
+    read_odd_typed!(read_i40_le, i64, read_int_n, 5, Little);
+    read_odd_typed!(read_i40_be, i64, read_int_n, 5, Big);
+
+    read_odd_typed!(read_u48, u64, read_uint_n, 6);
+    read_odd_typed!(read_u48_ne, u64, read_uint_n, 6, Native);
+    read_odd_typed!(read_u48_le, u64, read_uint_n, 6, Little);
+    read_odd_typed!(read_u48_be, u64, read_uint_n, 6, Big);
+
+    read_odd_typed!(read_i48, i64, read_int_n, 6);
+    read_odd_typed!(read_i48_ne, i64, read_int_n, 6, Native);
+    read_odd_typed!(read_i48_le, i64, read_int_n, 6, Little);
+    read_odd_typed!(read_i48_be, i64, read_int_n, 6, Big);
+
+    read_odd_typed!(read_u56, u64, read_uint_n, 7);
+    read_odd_typed!(read_u56_ne, u64, read_uint_n, 7, Native);
+    read_odd_typed!(read_u56_le, u64, read_uint_n, 7, Little);
+    read_odd_typed!(read_u56_be, u64, read_uint_n, 7, Big);
+
+    read_odd_typed!(read_i56, i64, read_int_n, 7);
+    read_odd_typed!(read_i56_ne, i64, read_int_n, 7, Native);
+    read_odd_typed!(read_i56_le, i64, read_int_n, 7, Little);
+    read_odd_typed!(read_i56_be, i64, read_int_n, 7, Big);
+
+    /// Reads `len` IEEE 754 half precision floats and converts them to `f32`.
+    fn read_f16_vec(&mut self, len: usize, endianess: Endianess) -> Result<Vec<f32>> {
+        let bits = float::read_u16_vec(self, len, endianess)?;
+        Ok(float::f16_to_f32_slice(&bits))
+    }
+
+    /// Reads `len` bfloat16 values and converts them to `f32`.
+    fn read_bf16_vec(&mut self, len: usize, endianess: Endianess) -> Result<Vec<f32>> {
+        let bits = float::read_u16_vec(self, len, endianess)?;
+        Ok(float::bf16_to_f32_slice(&bits))
+    }
+
+    read_float_typed!(read_f16, f32, read_u16, float::f16_to_f32);
+    read_float_typed!(read_f16_ne, f32, read_u16, float::f16_to_f32, Native);
+    read_float_typed!(read_f16_le, f32, read_u16, float::f16_to_f32, Little);
+    read_float_typed!(read_f16_be, f32, read_u16, float::f16_to_f32, Big);
+
+    read_float_typed!(read_bf16, f32, read_u16, float::bf16_to_f32);
+    read_float_typed!(read_bf16_ne, f32, read_u16, float::bf16_to_f32, Native);
+    read_float_typed!(read_bf16_le, f32, read_u16, float::bf16_to_f32, Little);
+    read_float_typed!(read_bf16_be, f32, read_u16, float::bf16_to_f32, Big);
+
+    read_float_bytes!(read_f80, float::f80_from_bytes);
+    read_float_bytes!(read_f80_ne, float::f80_from_bytes, Native);
+    read_float_bytes!(read_f80_le, float::f80_from_bytes, Little);
+    read_float_bytes!(read_f80_be, float::f80_from_bytes, Big);
+
+    read_float_typed!(read_ibm32, f64, read_u32, float::ibm32_to_f64);
+    read_float_typed!(read_ibm32_ne, f64, read_u32, float::ibm32_to_f64, Native);
+    read_float_typed!(read_ibm32_le, f64, read_u32, float::ibm32_to_f64, Little);
+    read_float_typed!(read_ibm32_be, f64, read_u32, float::ibm32_to_f64, Big);
+
+    read_float_typed!(read_ibm64, f64, read_u64, float::ibm64_to_f64);
+    read_float_typed!(read_ibm64_ne, f64, read_u64, float::ibm64_to_f64, Native);
+    read_float_typed!(read_ibm64_le, f64, read_u64, float::ibm64_to_f64, Little);
+    read_float_typed!(read_ibm64_be, f64, read_u64, float::ibm64_to_f64, Big);
+
+    /// Reads a VAX F_floating value and converts it to `f64`.
+    fn read_vax_f(&mut self) -> Result<f64> {
+        float::vax_f_to_f64(self.read_u8_array()?)
+    }
+
+    /// Reads a VAX D_floating value and converts it to `f64`.
+    fn read_vax_d(&mut self) -> Result<f64> {
+        float::vax_d_to_f64(self.read_u8_array()?)
+    }
+
+    /// Reads a VAX G_floating value and converts it to `f64`.
+    fn read_vax_g(&mut self) -> Result<f64> {
+        float::vax_g_to_f64(self.read_u8_array()?)
+    }
+
+    /// Reads a fixed-point number with the integer type `T` and `FRAC` fractional bits.
+    ///
+    /// It's recommended to use the typed wrappers like `read_fixed16_16` for common formats.
+    fn read_fixed<T, const FRAC: u32, const S: usize>(
+        &mut self,
+        endianess: Endianess,
+    ) -> Result<Fixed<T, FRAC>>
+    where
+        T: FixedBits + Primitive<T, S>,
+    {
+        use Endianess::*;
+        match endianess {
+            Little => self.read_le(),
+            Big => self.read_be(),
+            Native => self.read_ne(),
+        }
+    }
+
+    read_primitive_typed!(read_fixed16_16, Fixed16_16);
+    read_primitive_typed!(read_fixed16_16_ne, Fixed16_16, read_ne);
+    read_primitive_typed!(read_fixed16_16_le, Fixed16_16, read_le);
+    read_primitive_typed!(read_fixed16_16_be, Fixed16_16, read_be);
+
+    read_primitive_typed!(read_f2dot14, F2Dot14);
+    read_primitive_typed!(read_f2dot14_ne, F2Dot14, read_ne);
+    read_primitive_typed!(read_f2dot14_le, F2Dot14, read_le);
+    read_primitive_typed!(read_f2dot14_be, F2Dot14, read_be);
+
+    /// Reads a binary-coded decimal field of `len` bytes.
+    ///
+    /// Two digits are stored per byte with the high nibble first.
+    /// Fails for nibbles above 9 and for fields longer than 19 bytes, use `read_bcd_str` for those.
+    fn read_bcd(&mut self, len: usize) -> Result<u128> {
+        decimal::read_bcd(self, len)
+    }
+
+    /// Reads a binary-coded decimal field of `len` bytes as a decimal string.
+    ///
+    /// Leading zeros are removed.
+    fn read_bcd_str(&mut self, len: usize) -> Result<String> {
+        decimal::read_bcd_str(self, len)
+    }
+
+    /// Reads an IBM packed decimal (COMP-3) field of `len` bytes.
+    ///
+    /// The sign is stored in the last nibble, `C`, `A`, `E` and `F` are positive, `D` and `B` are negative.
+    /// Fails for invalid nibbles and for fields longer than 19 bytes, use `read_packed_decimal_str` for those.
+    fn read_packed_decimal(&mut self, len: usize) -> Result<i128> {
+        decimal::read_packed(self, len)
+    }
+
+    /// Reads an IBM packed decimal (COMP-3) field of `len` bytes as a decimal string.
+    fn read_packed_decimal_str(&mut self, len: usize) -> Result<String> {
+        decimal::read_packed_str(self, len)
+    }
+
     /// Reads a dynamic value from the reader.
     ///
     /// It's recommended to use the typed wrappers like `read_vec` instead of this method for cleaner code.
+    ///
+    /// The buffer grows while reading, so a truncated stream fails before `len` bytes are allocated.
     fn read_dynamic<T: crate::Dynamic>(&mut self, len: usize) -> Result<T> {
-        let mut buf = vec![0; len];
-        self.read_exact(&mut buf)?;
+        let buf =
+            read_bytes(self, len).map_err(|e| Error::for_value(e, None, type_name::<T>(), len))?;
         T::from_bytes(buf)
     }
 
+    /// Reads a plain-old-data value without decoding its fields.
+    fn read_pod<T: Pod>(&mut self) -> Result<T> {
+        let mut value = pod::zeroed::<T>();
+        self.read_exact(pod::bytes_of_mut(&mut value))
+            .map_err(|e| Error::for_value(e, None, type_name::<T>(), size_of::<T>()))?;
+        Ok(value)
+    }
+
+    /// Reads `len` plain-old-data values without decoding their fields.
+    ///
+    /// The vector grows while reading, so a truncated stream fails before `len` values are allocated.
+    /// Zero-sized types are rejected, like the slice casts of `pod` do.
+    fn read_pod_slice<T: Pod>(&mut self, len: usize) -> Result<Vec<T>> {
+        if size_of::<T>() == 0 {
+            return Err(Error::SizeMismatch {
+                offset: None,
+                type_name: type_name::<T>(),
+                expected: 0,
+                actual: 0,
+            });
+        }
+        let size = len
+            .checked_mul(size_of::<T>())
+            .ok_or_else(|| Error::OutOfRange {
+                offset: None,
+                value: len as i128,
+                type_name: type_name::<T>(),
+            })?;
+        let chunk = (8192 / size_of::<T>()).max(1);
+        let mut values = Vec::with_capacity(len.min(chunk));
+        while values.len() < len {
+            let start = values.len();
+            values.resize(start + chunk.min(len - start), pod::zeroed());
+            self.read_exact(pod::slice_bytes_mut(&mut values[start..]))
+                .map_err(|e| Error::for_value(e, None, type_name::<[T]>(), size))?;
+        }
+        Ok(values)
+    }
+
     fn copy(&mut self, len: u64, mut target: &mut dyn Write) -> Result<()> {
         let mut limited = self.take(len);
         std::io::copy(&mut limited, &mut target)?;
         Ok(())
     }
 
+    /// Decompresses `len` compressed bytes in the given format and writes the result to `target`.
+    ///
+    /// Bytes after the end of the compressed stream are skipped, so the reader always ends up after the `len` bytes.
+    fn copy_decompressed(
+        &mut self,
+        len: u64,
+        format: Format,
+        target: &mut dyn Write,
+    ) -> Result<()> {
+        let mut limited = self.take(len);
+        compress::decompress(&mut limited, format, u64::MAX, target)?;
+        std::io::copy(&mut limited, &mut std::io::sink())?;
+        Ok(())
+    }
+
+    /// Reads a zlib stream of `compressed_len` bytes and returns the decompressed data.
+    ///
+    /// The reader always ends up after the `compressed_len` bytes.
+    fn read_zlib_block(&mut self, compressed_len: u64) -> Result<Vec<u8>> {
+        self.read_zlib_block_with_limit(compressed_len, u64::MAX)
+    }
+
+    /// Like `read_zlib_block`, but fails once more than `limit` bytes are decompressed.
+    fn read_zlib_block_with_limit(&mut self, compressed_len: u64, limit: u64) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        let mut limited = self.take(compressed_len);
+        compress::decompress(&mut limited, Format::Zlib, limit, &mut data)?;
+        std::io::copy(&mut limited, &mut std::io::sink())?;
+        Ok(data)
+    }
+
     fn copy_to(
         &mut self,
         len: u64,
@@ -0,0 +1,209 @@
+use crate::{Error, Result};
+use std::io::{Read, Seek, SeekFrom};
+use std::str::FromStr;
+
+const CHUNK: usize = 65536;
+
+/// Byte pattern with optional wildcards for the search methods like `ReadValAt::find`.
+///
+/// Converts from byte slices and arrays, wildcard patterns are parsed from strings like `"4D 5A ?? ?? 50 45"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    bytes: Vec<u8>,
+    mask: Vec<u8>,
+}
+
+impl Pattern {
+    /// Creates a pattern that only matches exactly these bytes.
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        let bytes = bytes.into();
+        let mask = vec![0xff; bytes.len()];
+        Self { bytes, mask }
+    }
+
+    /// Creates a pattern that matches bytes where `byte & mask` equals `bytes & mask`.
+    ///
+    /// Panics if the lengths differ.
+    pub fn with_mask(bytes: impl Into<Vec<u8>>, mask: impl Into<Vec<u8>>) -> Self {
+        let (bytes, mask): (Vec<u8>, Vec<u8>) = (bytes.into(), mask.into());
+        assert_eq!(bytes.len(), mask.len(), "pattern and mask lengths differ");
+        let bytes = bytes
+            .iter()
+            .zip(&mask)
+            .map(|(byte, mask)| byte & mask)
+            .collect();
+        Self { bytes, mask }
+    }
+
+    /// Parses hex bytes separated by whitespace, `?` is a wildcard for a nibble or a whole byte.
+    ///
+    /// For example `"4D 5A ?? ?? 50 45"` or `"ff?0 ?"`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = |reason| Error::InvalidEncoding {
+            offset: None,
+            type_name: "pattern",
+            reason,
+        };
+        let (mut bytes, mut mask) = (Vec::new(), Vec::new());
+        for token in text.split_ascii_whitespace() {
+            if token == "?" {
+                bytes.push(0);
+                mask.push(0);
+                continue;
+            }
+            if token.len() % 2 != 0 {
+                return Err(invalid("odd number of hex digits"));
+            }
+            for pair in token.as_bytes().chunks(2) {
+                let (mut byte, mut byte_mask) = (0, 0);
+                for char in pair {
+                    byte <<= 4;
+                    byte_mask <<= 4;
+                    if *char != b'?' {
+                        byte |= (*char as char)
+                            .to_digit(16)
+                            .ok_or_else(|| invalid("invalid hex digit"))?
+                            as u8;
+                        byte_mask |= 0xf;
+                    }
+                }
+                bytes.push(byte);
+                mask.push(byte_mask);
+            }
+        }
+        Ok(Self { bytes, mask })
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns whether the pattern matches the start of `data`.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.len() >= self.len()
+            && data
+                .iter()
+                .zip(self.bytes.iter().zip(&self.mask))
+                .all(|(byte, (expected, mask))| byte & mask == *expected)
+    }
+
+    /// Returns the positions in `data` where a match can start, the full pattern may not fit.
+    fn candidates<'a>(&'a self, data: &'a [u8]) -> impl DoubleEndedIterator<Item = usize> + 'a {
+        // start with the first exact byte, which skips most positions quickly
+        let anchor = self.mask.iter().position(|mask| *mask == 0xff);
+        (0..data.len()).filter(move |pos| match anchor {
+            Some(anchor) => data.get(pos + anchor) == Some(&self.bytes[anchor]),
+            None => true,
+        })
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+impl From<&[u8]> for Pattern {
+    fn from(bytes: &[u8]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for Pattern {
+    fn from(bytes: &[u8; N]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Pattern {
+    fn from(bytes: [u8; N]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<Vec<u8>> for Pattern {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<&Pattern> for Pattern {
+    fn from(pattern: &Pattern) -> Self {
+        pattern.clone()
+    }
+}
+
+/// Reads as many bytes as possible from `pos` up to `len` bytes.
+fn read_chunk<R: Read + Seek + ?Sized>(reader: &mut R, pos: u64, len: usize) -> Result<Vec<u8>> {
+    reader.seek(SeekFrom::Start(pos))?;
+    let mut buf = Vec::with_capacity(len);
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Calls `found` for every non-overlapping match starting in `start..end` until it returns `false`.
+///
+/// The chunks overlap by the pattern length, so matches across chunk boundaries are found as well.
+pub(crate) fn scan<R: Read + Seek + ?Sized>(
+    reader: &mut R,
+    pattern: &Pattern,
+    start: u64,
+    end: u64,
+    mut found: impl FnMut(u64) -> bool,
+) -> Result<()> {
+    let mut pos = start;
+    while pos < end {
+        let len = (end - pos).min(CHUNK as u64) as usize;
+        let chunk = read_chunk(reader, pos, len + pattern.len().saturating_sub(1))?;
+        let mut next = 0;
+        for candidate in pattern
+            .candidates(&chunk)
+            .take_while(|candidate| *candidate < len)
+        {
+            if candidate >= next && pattern.matches(&chunk[candidate..]) {
+                if !found(pos + candidate as u64) {
+                    return Ok(());
+                }
+                next = candidate + pattern.len().max(1);
+            }
+        }
+        if chunk.len() < len + pattern.len().saturating_sub(1) {
+            break;
+        }
+        // skip the part of the overlap that was already covered by a match
+        pos += len.max(next) as u64;
+    }
+    Ok(())
+}
+
+/// Returns the last match starting in `start..end`, reading the stream backwards.
+pub(crate) fn scan_back<R: Read + Seek + ?Sized>(
+    reader: &mut R,
+    pattern: &Pattern,
+    start: u64,
+    end: u64,
+) -> Result<Option<u64>> {
+    let mut chunk_end = end;
+    while chunk_end > start {
+        let pos = chunk_end.saturating_sub(CHUNK as u64).max(start);
+        let len = (chunk_end - pos) as usize;
+        let chunk = read_chunk(reader, pos, len + pattern.len().saturating_sub(1))?;
+        let found = pattern
+            .candidates(&chunk)
+            .rev()
+            .filter(|candidate| *candidate < len)
+            .find(|candidate| pattern.matches(&chunk[*candidate..]));
+        if let Some(candidate) = found {
+            return Ok(Some(pos + candidate as u64));
+        }
+        chunk_end = pos;
+    }
+    Ok(None)
+}
@@ -0,0 +1,144 @@
+use crate::{Endianess, Primitive, Result};
+use std::fmt;
+use std::marker::PhantomData;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Byte order marker for the endian-tagged types like `U32<Le>`.
+pub trait ByteOrder: sealed::Sealed + Copy + Default + 'static {
+    const ENDIANESS: Endianess;
+}
+
+/// Little endian byte order marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Le;
+
+/// Big endian byte order marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Be;
+
+impl sealed::Sealed for Le {}
+impl sealed::Sealed for Be {}
+
+impl ByteOrder for Le {
+    const ENDIANESS: Endianess = Endianess::Little;
+}
+
+impl ByteOrder for Be {
+    const ENDIANESS: Endianess = Endianess::Big;
+}
+
+macro_rules! tagged {
+    ($name:ident, $type:ty, $size:literal $(, $derive:ident)*) => {
+        /// Value stored in the byte order `O`, so structs can mirror a file layout exactly.
+        ///
+        /// Reading and writing it with `read_ne` or `write_ne` keeps the bytes as they are,
+        /// `get` and `set` convert from and to the native value.
+        #[repr(transparent)]
+        #[derive(Clone, Copy, PartialEq, Default $(, $derive)*)]
+        pub struct $name<O: ByteOrder> {
+            bytes: [u8; $size],
+            order: PhantomData<O>,
+        }
+
+        impl<O: ByteOrder> $name<O> {
+            pub fn new(value: $type) -> Self {
+                let mut tagged = Self::from_bytes([0; $size]);
+                tagged.set(value);
+                tagged
+            }
+
+            /// Creates the value from bytes that are already in the byte order `O`.
+            pub const fn from_bytes(bytes: [u8; $size]) -> Self {
+                Self {
+                    bytes,
+                    order: PhantomData,
+                }
+            }
+
+            /// Returns the bytes in the byte order `O`.
+            pub const fn to_bytes(self) -> [u8; $size] {
+                self.bytes
+            }
+
+            pub fn get(self) -> $type {
+                match O::ENDIANESS {
+                    Endianess::Little => <$type>::from_le_bytes(self.bytes),
+                    Endianess::Big => <$type>::from_be_bytes(self.bytes),
+                    Endianess::Native => <$type>::from_ne_bytes(self.bytes),
+                }
+            }
+
+            pub fn set(&mut self, value: $type) {
+                self.bytes = match O::ENDIANESS {
+                    Endianess::Little => value.to_le_bytes(),
+                    Endianess::Big => value.to_be_bytes(),
+                    Endianess::Native => value.to_ne_bytes(),
+                };
+            }
+        }
+
+        impl<O: ByteOrder> From<$type> for $name<O> {
+            fn from(value: $type) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl<O: ByteOrder> From<$name<O>> for $type {
+            fn from(value: $name<O>) -> Self {
+                value.get()
+            }
+        }
+
+        impl<O: ByteOrder> fmt::Debug for $name<O> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        // the bytes are stored in the byte order `O`, so they are never swapped
+        impl<O: ByteOrder> Primitive<$name<O>, $size> for $name<O> {
+            fn from_ne_bytes(bytes: [u8; $size]) -> Result<Self> {
+                Ok(Self::from_bytes(bytes))
+            }
+
+            fn from_le_bytes(bytes: [u8; $size]) -> Result<Self> {
+                Ok(Self::from_bytes(bytes))
+            }
+
+            fn from_be_bytes(bytes: [u8; $size]) -> Result<Self> {
+                Ok(Self::from_bytes(bytes))
+            }
+
+            fn to_ne_bytes(self) -> [u8; $size] {
+                self.bytes
+            }
+
+            fn to_le_bytes(self) -> [u8; $size] {
+                self.bytes
+            }
+
+            fn to_be_bytes(self) -> [u8; $size] {
+                self.bytes
+            }
+        }
+    };
+}
+
+// unsigned integers
+tagged!(U16, u16, 2, Eq, Hash);
+tagged!(U32, u32, 4, Eq, Hash);
+tagged!(U64, u64, 8, Eq, Hash);
+tagged!(U128, u128, 16, Eq, Hash);
+
+// signed integers
+tagged!(I16, i16, 2, Eq, Hash);
+tagged!(I32, i32, 4, Eq, Hash);
+tagged!(I64, i64, 8, Eq, Hash);
+tagged!(I128, i128, 16, Eq, Hash);
+
+// floating point numbers (compared by their bytes)
+tagged!(F32, f32, 4);
+tagged!(F64, f64, 8);
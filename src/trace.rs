@@ -0,0 +1,226 @@
+use crate::{Endianess, Primitive, ReadVal, Result};
+use std::any::type_name;
+use std::fmt::Debug;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+
+/// Read recorded by a `Tracer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub offset: u64,
+    pub len: u64,
+    /// Type of the decoded value, `bytes` for untyped reads.
+    pub type_name: &'static str,
+    /// Decoded value formatted with `Debug`, only known for reads made with `Tracer::read_typed` or `Tracer::traced`.
+    pub value: Option<String>,
+    pub label: Option<String>,
+}
+
+/// Wrapper for `Read + Seek` that records every read, e.g. to debug a parser or to build a coverage map of a format.
+///
+/// Reads made through the wrapper, including all reads of `ReadVal` and `ReadValAt`, are recorded
+/// as untyped `bytes` entries. Use `read_typed` to record a primitive with its type and decoded value,
+/// or wrap reads in `traced` to record them as one entry with the returned value and a label.
+pub struct Tracer<R: Read + Seek> {
+    inner: R,
+    pos: Option<u64>,
+    entries: Vec<TraceEntry>,
+    covered: Vec<Range<u64>>,
+    frames: Vec<Option<Range<u64>>>,
+}
+
+impl<R: Read + Seek> Tracer<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pos: None,
+            entries: Vec::new(),
+            covered: Vec::new(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Runs `read` and records everything it reads as one entry with the returned value and `label`.
+    ///
+    /// The entry spans from the first to the last byte read, nested calls record their own entries.
+    /// Nothing is recorded if `read` fails.
+    pub fn traced<T: Debug>(
+        &mut self,
+        label: impl Into<String>,
+        read: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        self.record_value(Some(label.into()), read)
+    }
+
+    /// Reads a primitive value using the specified byte order and records it with its type and decoded value.
+    pub fn read_typed<T: Primitive<U, S> + Debug, U, const S: usize>(
+        &mut self,
+        endianess: Endianess,
+    ) -> Result<T> {
+        self.record_value(None, |reader| match endianess {
+            Endianess::Little => reader.read_le(),
+            Endianess::Big => reader.read_be(),
+            Endianess::Native => reader.read_ne(),
+        })
+    }
+
+    /// Returns the recorded reads in the order they finished.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Removes the recorded reads and the coverage.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.covered.clear();
+    }
+
+    /// Returns the byte ranges of the stream that were never read.
+    pub fn gaps(&mut self) -> Result<Vec<Range<u64>>> {
+        let pos_before = self.inner.stream_position()?;
+        let len = self.inner.seek(SeekFrom::End(0))?;
+        self.inner.seek(SeekFrom::Start(pos_before))?;
+
+        let mut gaps = Vec::new();
+        let mut start = 0;
+        for range in &self.covered {
+            if range.start > start {
+                gaps.push(start..range.start);
+            }
+            start = range.end;
+        }
+        if start < len {
+            gaps.push(start..len);
+        }
+        Ok(gaps)
+    }
+
+    /// Writes the entries as JSON lines with the keys `offset`, `len`, `type`, `value` and `label`.
+    pub fn write_json_lines(&self, target: &mut dyn Write) -> Result<()> {
+        for entry in &self.entries {
+            writeln!(
+                target,
+                r#"{{"offset":{},"len":{},"type":{},"value":{},"label":{}}}"#,
+                entry.offset,
+                entry.len,
+                json_string(Some(entry.type_name)),
+                json_string(entry.value.as_deref()),
+                json_string(entry.label.as_deref()),
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// **Note:** reads and seeks on the inner reader are not recorded and make the tracked position incorrect.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn record_value<T: Debug>(
+        &mut self,
+        label: Option<String>,
+        read: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        self.frames.push(None);
+        let result = read(self);
+        let range = self.frames.pop().flatten();
+
+        let value = result?;
+        if let Some(range) = range {
+            self.entries.push(TraceEntry {
+                offset: range.start,
+                len: range.end - range.start,
+                type_name: type_name::<T>(),
+                value: Some(format!("{value:?}")),
+                label,
+            });
+        }
+        Ok(value)
+    }
+
+    fn record(&mut self, range: Range<u64>) {
+        for frame in &mut self.frames {
+            *frame = Some(match frame.take() {
+                Some(known) => known.start.min(range.start)..known.end.max(range.end),
+                None => range.clone(),
+            });
+        }
+        if self.frames.is_empty() {
+            self.entries.push(TraceEntry {
+                offset: range.start,
+                len: range.end - range.start,
+                type_name: "bytes",
+                value: None,
+                label: None,
+            });
+        }
+
+        // keep the covered ranges sorted and merged
+        let index = self
+            .covered
+            .partition_point(|known| known.end < range.start);
+        let mut merged = range;
+        while let Some(known) = self
+            .covered
+            .get(index)
+            .filter(|known| known.start <= merged.end)
+        {
+            merged = known.start.min(merged.start)..known.end.max(merged.end);
+            self.covered.remove(index);
+        }
+        self.covered.insert(index, merged);
+    }
+}
+
+fn json_string(value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return "null".to_string();
+    };
+    let mut json = String::from("\"");
+    for char in value.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            char if char < ' ' => json.push_str(&format!("\\u{:04x}", char as u32)),
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl<R: Read + Seek> Read for Tracer<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let offset = match self.pos {
+            Some(pos) => pos,
+            None => self.inner.stream_position()?,
+        };
+        let read = self.inner.read(buf)?;
+        self.pos = Some(offset + read as u64);
+        if read > 0 {
+            self.record(offset..offset + read as u64);
+        }
+        Ok(read)
+    }
+}
+
+impl<R: Read + Seek> Seek for Tracer<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = self.inner.seek(pos)?;
+        self.pos = Some(pos);
+        Ok(pos)
+    }
+}
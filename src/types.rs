@@ -1,6 +1,35 @@
+use crate::{Error, Result};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Endianess {
     Little,
     Big,
     Native,
 }
+
+/// How integer values are decoded as `bool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoolMode {
+    /// Only the lowest bit is checked, this is what `read_bool` does.
+    #[default]
+    Lsb,
+    /// Only 0 and 1 are allowed, anything else is an error.
+    Strict,
+    /// Any non-zero value is `true`, like in C.
+    NonZero,
+}
+
+impl BoolMode {
+    /// Decodes an integer value as `bool`.
+    pub fn decode(self, value: u64) -> Result<bool> {
+        match (self, value) {
+            (BoolMode::Lsb, _) => Ok(value & 1 == 1),
+            (BoolMode::Strict, 0 | 1) | (BoolMode::NonZero, _) => Ok(value != 0),
+            (BoolMode::Strict, _) => Err(Error::InvalidEncoding {
+                offset: None,
+                type_name: "bool",
+                reason: "value is neither 0 nor 1",
+            }),
+        }
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{Endianess, Primitive, Result};
-use std::io::{Seek, SeekFrom::Start as SeekPos, Write};
+use crate::{
+    Endianess, Error, F2Dot14, Fixed, Fixed16_16, FixedBits, Primitive, Result,
+    checksum::Algorithm, decimal, float, odd, primitive,
+};
+use std::any::type_name;
+use std::io::{Read, Seek, SeekFrom::Start as SeekPos, Write};
 
 macro_rules! write_primitive {
     ($fn_name:ident, $write_fn_name:ident) => {
@@ -14,7 +18,9 @@ macro_rules! write_primitive {
             let pos_before = self.stream_position()?;
             self.seek(SeekPos(pos as u64))?;
 
-            let response = self.write_all(&data.$write_fn_name());
+            let response = self
+                .write_all(&data.$write_fn_name())
+                .map_err(|e| Error::for_value(e, Some(pos as u64), type_name::<T>(), S));
 
             self.seek(SeekPos(pos_before))?;
             response
@@ -56,13 +62,119 @@ macro_rules! write_dynamic_typed {
         fn $fn_name(&mut self, pos: usize, data: $return_type) -> Result<()> {
             let pos_before = self.stream_position()?;
             self.seek(SeekPos(pos as u64))?;
-            let response = self.write_dynamic_at(data);
+            let response = self
+                .write_dynamic_at(data)
+                .map_err(|e| e.with_offset(Some(pos as u64)));
             self.seek(SeekPos(pos_before))?;
             response
         }
     };
 }
 
+macro_rules! write_odd_typed {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $bytes:literal) => {
+        /// Typed wrapper around `write_uint_n_at` or `write_int_n_at`.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess, data: $data_type) -> Result<()> {
+            self.$write_fn(pos, $bytes, endianess, data as _)
+        }
+    };
+
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $bytes:literal, $endianess:ident) => {
+        /// Typed wrapper around `write_uint_n_at` or `write_int_n_at`.
+        fn $fn_name(&mut self, pos: usize, data: $data_type) -> Result<()> {
+            self.$write_fn(pos, $bytes, Endianess::$endianess, data as _)
+        }
+    };
+}
+
+macro_rules! write_odd {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident) => {
+        /// Writes an integer with a width of 1 to 8 bytes at a specific position, checking that it fits.
+        ///
+        /// It's recommended to use the typed wrappers like `write_u24_le_at` instead of this method for cleaner code.
+        fn $fn_name(
+            &mut self,
+            pos: usize,
+            bytes: usize,
+            endianess: Endianess,
+            data: $data_type,
+        ) -> Result<()> {
+            let pos_before = self.stream_position()?;
+            self.seek(SeekPos(pos as u64))?;
+            let response = odd::$write_fn(self, bytes, endianess, data)
+                .map_err(|e| e.with_offset(Some(pos as u64)));
+            self.seek(SeekPos(pos_before))?;
+            response
+        }
+    };
+}
+
+macro_rules! write_float_typed {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path) => {
+        /// Converts a floating point value to another format and writes it at a specific position.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess, data: $data_type) -> Result<()> {
+            self.$write_fn(pos, endianess, $convert(data))
+        }
+    };
+
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path, $endianess:ident) => {
+        /// Converts a floating point value to another format and writes it at a specific position.
+        fn $fn_name(&mut self, pos: usize, data: $data_type) -> Result<()> {
+            self.$write_fn(pos, Endianess::$endianess, $convert(data))
+        }
+    };
+}
+
+macro_rules! write_float_checked {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path) => {
+        /// Converts a floating point value to another format and writes it at a specific position,
+        /// failing if it can't be represented.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess, data: $data_type) -> Result<()> {
+            let data = $convert(data).map_err(|e| e.with_offset(Some(pos as u64)))?;
+            self.$write_fn(pos, endianess, data)
+        }
+    };
+
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path, $endianess:ident) => {
+        /// Converts a floating point value to another format and writes it at a specific position,
+        /// failing if it can't be represented.
+        fn $fn_name(&mut self, pos: usize, data: $data_type) -> Result<()> {
+            let data = $convert(data).map_err(|e| e.with_offset(Some(pos as u64)))?;
+            self.$write_fn(pos, Endianess::$endianess, data)
+        }
+    };
+}
+
+macro_rules! write_float_bytes {
+    ($fn_name:ident, $convert:path) => {
+        /// Converts a floating point value to another format and writes it at a specific position.
+        fn $fn_name(&mut self, pos: usize, endianess: Endianess, data: f64) -> Result<()> {
+            self.write_u8_array_at(pos, $convert(data, endianess))
+        }
+    };
+
+    ($fn_name:ident, $convert:path, $endianess:ident) => {
+        /// Converts a floating point value to another format and writes it at a specific position.
+        fn $fn_name(&mut self, pos: usize, data: f64) -> Result<()> {
+            self.write_u8_array_at(pos, $convert(data, Endianess::$endianess))
+        }
+    };
+}
+
+macro_rules! write_decimal {
+    ($(#[$attr:meta])* $fn_name:ident, $data_type:ty, $write_fn:ident) => {
+        $(#[$attr])*
+        fn $fn_name(&mut self, pos: usize, len: usize, data: $data_type) -> Result<()> {
+            let pos_before = self.stream_position()?;
+            self.seek(SeekPos(pos as u64))?;
+            let result = decimal::$write_fn(self, len, data)
+                .map_err(|e| e.with_offset(Some(pos as u64)));
+            self.seek(SeekPos(pos_before))?;
+            result
+        }
+    };
+}
+
 /// Extension trait for `Write + Seek` that provides methods for writeing supported value types.
 pub trait WriteValAt: Write + Seek {
     write_primitive_typed!(write_u8_at, u8, write_ne_at);
@@ -88,6 +200,28 @@ pub trait WriteValAt: Write + Seek {
 
     write_primitive_typed!(write_bool_at, bool, write_ne_at);
 
+    /// Writes a bool as a 2 byte value of 0 or 1 at a specific position.
+    fn write_bool16_at(&mut self, pos: usize, endianess: Endianess, data: bool) -> Result<()> {
+        self.write_u16_at(pos, endianess, data as u16)
+    }
+
+    /// Writes a bool as a 4 byte value of 0 or 1 at a specific position, like the Windows `BOOL`.
+    fn write_bool32_at(&mut self, pos: usize, endianess: Endianess, data: bool) -> Result<()> {
+        self.write_u32_at(pos, endianess, data as u32)
+    }
+
+    write_primitive_typed!(write_char_at, char);
+
+    /// Writes a char encoded as UTF-8 at a specific position, which takes 1 to 4 bytes.
+    fn write_char_utf8_at(&mut self, pos: usize, data: char) -> Result<()> {
+        let pos_before = self.stream_position()?;
+        self.seek(SeekPos(pos as u64))?;
+        let result =
+            primitive::write_utf8_char(self, data).map_err(|e| e.with_offset(Some(pos as u64)));
+        self.seek(SeekPos(pos_before))?;
+        result
+    }
+
     write_dynamic_typed!(write_vec_at, Vec<u8>);
     write_dynamic_typed!(write_str_at, String);
 
@@ -143,11 +277,252 @@ pub trait WriteValAt: Write + Seek {
     write_primitive_typed!(write_f64_le_at, f64, write_le_at);
     write_primitive_typed!(write_f64_be_at, f64, write_be_at);
 
+    write_primitive_typed!(write_char_ne_at, char, write_ne_at);
+    write_primitive_typed!(write_char_le_at, char, write_le_at);
+    write_primitive_typed!(write_char_be_at, char, write_be_at);
+
+    write_odd!(write_uint_n_at, u64, write_uint);
+    write_odd!(write_int_n_at, i64, write_int);
+
+    write_odd_typed!(write_u24_at, u32, write_uint_n_at, 3);
+    write_odd_typed!(write_u24_ne_at, u32, write_uint_n_at, 3, Native);
+    write_odd_typed!(write_u24_le_at, u32, write_uint_n_at, 3, Little);
+    write_odd_typed!(write_u24_be_at, u32, write_uint_n_at, 3, Big);
+
+    write_odd_typed!(write_i24_at, i32, write_int_n_at, 3);
+    write_odd_typed!(write_i24_ne_at, i32, write_int_n_at, 3, Native);
+    write_odd_typed!(write_i24_le_at, i32, write_int_n_at, 3, Little);
+    write_odd_typed!(write_i24_be_at, i32, write_int_n_at, 3, Big);
+
+    write_odd_typed!(write_u40_at, u64, write_uint_n_at, 5);
+    write_odd_typed!(write_u40_ne_at, u64, write_uint_n_at, 5, Native);
+    write_odd_typed!(write_u40_le_at, u64, write_uint_n_at, 5, Little);
+    write_odd_typed!(write_u40_be_at, u64, write_uint_n_at, 5, Big);
+
+    write_odd_typed!(write_i40_at, i64, write_int_n_at, 5);
+    write_odd_typed!(write_i40_ne_at, i64, write_int_n_at, 5, Native);
+    write_odd_typed!(write_i40_le_at, i64, write_int_n_at, 5, Little);
+    write_odd_typed!(write_i40_be_at, i64, write_int_n_at, 5, Big);
+
+    write_odd_typed!(write_u48_at, u64, write_uint_n_at, 6);
+    write_odd_typed!(write_u48_ne_at, u64, write_uint_n_at, 6, Native);
+    write_odd_typed!(write_u48_le_at, u64, write_uint_n_at, 6, Little);
+    write_odd_typed!(write_u48_be_at, u64, write_uint_n_at, 6, Big);
+
+    write_odd_typed!(write_i48_at, i64, write_int_n_at, 6);
+    write_odd_typed!(write_i48_ne_at, i64, write_int_n_at, 6, Native);
+    write_odd_typed!(write_i48_le_at, i64, write_int_n_at, 6, Little);
+    write_odd_typed!(write_i48_be_at, i64, write_int_n_at, 6, Big);
+
+    write_odd_typed!(write_u56_at, u64, write_uint_n_at, 7);
+    write_odd_typed!(write_u56_ne_at, u64, write_uint_n_at, 7, Native);
+    write_odd_typed!(write_u56_le_at, u64, write_uint_n_at, 7, Little);
+    write_odd_typed!(write_u56_be_at, u64, write_uint_n_at, 7, Big);
+
+    write_odd_typed!(write_i56_at, i64, write_int_n_at, 7);
+    write_odd_typed!(write_i56_ne_at, i64, write_int_n_at, 7, Native);
+    write_odd_typed!(write_i56_le_at, i64, write_int_n_at, 7, Little);
+    write_odd_typed!(write_i56_be_at, i64, write_int_n_at, 7, Big);
+
+    write_float_typed!(write_f16_at, f32, write_u16_at, float::f32_to_f16);
+    write_float_typed!(
+        write_f16_ne_at,
+        f32,
+        write_u16_at,
+        float::f32_to_f16,
+        Native
+    );
+    write_float_typed!(
+        write_f16_le_at,
+        f32,
+        write_u16_at,
+        float::f32_to_f16,
+        Little
+    );
+    write_float_typed!(write_f16_be_at, f32, write_u16_at, float::f32_to_f16, Big);
+
+    write_float_typed!(write_bf16_at, f32, write_u16_at, float::f32_to_bf16);
+    write_float_typed!(
+        write_bf16_ne_at,
+        f32,
+        write_u16_at,
+        float::f32_to_bf16,
+        Native
+    );
+    write_float_typed!(
+        write_bf16_le_at,
+        f32,
+        write_u16_at,
+        float::f32_to_bf16,
+        Little
+    );
+    write_float_typed!(write_bf16_be_at, f32, write_u16_at, float::f32_to_bf16, Big);
+
+    write_float_bytes!(write_f80_at, float::f80_to_bytes);
+    write_float_bytes!(write_f80_ne_at, float::f80_to_bytes, Native);
+    write_float_bytes!(write_f80_le_at, float::f80_to_bytes, Little);
+    write_float_bytes!(write_f80_be_at, float::f80_to_bytes, Big);
+
+    write_float_checked!(write_ibm32_at, f64, write_u32_at, float::f64_to_ibm32);
+    write_float_checked!(
+        write_ibm32_ne_at,
+        f64,
+        write_u32_at,
+        float::f64_to_ibm32,
+        Native
+    );
+    write_float_checked!(
+        write_ibm32_le_at,
+        f64,
+        write_u32_at,
+        float::f64_to_ibm32,
+        Little
+    );
+    write_float_checked!(
+        write_ibm32_be_at,
+        f64,
+        write_u32_at,
+        float::f64_to_ibm32,
+        Big
+    );
+
+    write_float_checked!(write_ibm64_at, f64, write_u64_at, float::f64_to_ibm64);
+    write_float_checked!(
+        write_ibm64_ne_at,
+        f64,
+        write_u64_at,
+        float::f64_to_ibm64,
+        Native
+    );
+    write_float_checked!(
+        write_ibm64_le_at,
+        f64,
+        write_u64_at,
+        float::f64_to_ibm64,
+        Little
+    );
+    write_float_checked!(
+        write_ibm64_be_at,
+        f64,
+        write_u64_at,
+        float::f64_to_ibm64,
+        Big
+    );
+
+    /// Converts a value to a VAX F_floating value and writes it at a specific position,
+    /// failing if it can't be represented.
+    fn write_vax_f_at(&mut self, pos: usize, data: f64) -> Result<()> {
+        let data = float::f64_to_vax_f(data).map_err(|e| e.with_offset(Some(pos as u64)))?;
+        self.write_u8_array_at(pos, data)
+    }
+
+    /// Converts a value to a VAX D_floating value and writes it at a specific position,
+    /// failing if it can't be represented.
+    fn write_vax_d_at(&mut self, pos: usize, data: f64) -> Result<()> {
+        let data = float::f64_to_vax_d(data).map_err(|e| e.with_offset(Some(pos as u64)))?;
+        self.write_u8_array_at(pos, data)
+    }
+
+    /// Converts a value to a VAX G_floating value and writes it at a specific position,
+    /// failing if it can't be represented.
+    fn write_vax_g_at(&mut self, pos: usize, data: f64) -> Result<()> {
+        let data = float::f64_to_vax_g(data).map_err(|e| e.with_offset(Some(pos as u64)))?;
+        self.write_u8_array_at(pos, data)
+    }
+
+    /// Writes a fixed-point number with the integer type `T` and `FRAC` fractional bits at a specific position.
+    ///
+    /// It's recommended to use the typed wrappers like `write_fixed16_16_at` for common formats.
+    fn write_fixed_at<T, const FRAC: u32, const S: usize>(
+        &mut self,
+        pos: usize,
+        endianess: Endianess,
+        data: Fixed<T, FRAC>,
+    ) -> Result<()>
+    where
+        T: FixedBits + Primitive<T, S>,
+    {
+        use Endianess::*;
+        match endianess {
+            Little => self.write_le_at(pos, data),
+            Big => self.write_be_at(pos, data),
+            Native => self.write_ne_at(pos, data),
+        }
+    }
+
+    write_primitive_typed!(write_fixed16_16_at, Fixed16_16);
+    write_primitive_typed!(write_fixed16_16_ne_at, Fixed16_16, write_ne_at);
+    write_primitive_typed!(write_fixed16_16_le_at, Fixed16_16, write_le_at);
+    write_primitive_typed!(write_fixed16_16_be_at, Fixed16_16, write_be_at);
+
+    write_primitive_typed!(write_f2dot14_at, F2Dot14);
+    write_primitive_typed!(write_f2dot14_ne_at, F2Dot14, write_ne_at);
+    write_primitive_typed!(write_f2dot14_le_at, F2Dot14, write_le_at);
+    write_primitive_typed!(write_f2dot14_be_at, F2Dot14, write_be_at);
+
+    write_decimal!(
+        /// Writes a binary-coded decimal field of `len` bytes at a specific position.
+        ///
+        /// The value is padded with leading zeros.
+        /// Fails if the value has too many digits or the field is longer than 19 bytes.
+        write_bcd_at,
+        u128,
+        write_bcd
+    );
+
+    write_decimal!(
+        /// Writes an IBM packed decimal (COMP-3) field of `len` bytes at a specific position.
+        ///
+        /// The sign nibble is `C` or `D`.
+        /// Fails if the value has too many digits or the field is longer than 19 bytes.
+        write_packed_decimal_at,
+        i128,
+        write_packed
+    );
+
+    /// Runs sequential writes and records the position where they started in their error.
+    ///
+    /// Errors that already know their offset keep it, e.g. from `*_at` methods or nested calls.
+    fn write_positioned<T>(&mut self, write: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let pos = self.stream_position()?;
+        write(self).map_err(|e| e.with_offset(Some(pos)))
+    }
+
+    /// Computes the checksum of `len` bytes at `pos` and writes it at `target`, e.g. for header checksums.
+    ///
+    /// The checksum is written with its own width (see `Algorithm::size`) and doesn't move the cursor.
+    fn write_checksum_at(
+        &mut self,
+        pos: u64,
+        len: u64,
+        algorithm: Algorithm,
+        target: u64,
+        endianess: Endianess,
+    ) -> Result<()>
+    where
+        Self: Read,
+    {
+        let pos_before = self.stream_position()?;
+        self.seek(SeekPos(pos))?;
+        let response = algorithm
+            .compute(self, len)
+            .map_err(|e| e.with_offset(Some(pos)))
+            .and_then(|value| {
+                self.seek(SeekPos(target))?;
+                odd::write_uint(self, algorithm.size(), endianess, value)
+                    .map_err(|e| e.with_offset(Some(target)))
+            });
+        self.seek(SeekPos(pos_before))?;
+        response
+    }
+
     /// Writes a dynamic value to the writeer.
     ///
     /// It's recommended to use the typed wrappers like `write_vec_at` instead of this method for cleaner code.
     fn write_dynamic_at<T: crate::Dynamic>(&mut self, data: T) -> Result<()> {
-        self.write_all(data.into_bytes()?)
+        let bytes = data.into_bytes()?;
+        self.write_all(bytes)
+            .map_err(|e| Error::for_value(e, None, type_name::<T>(), bytes.len()))
     }
 }
 
@@ -1,6 +1,10 @@
 #[cfg(feature = "vli")]
 use crate::variable;
-use crate::{Endianess, Primitive, Result};
+use crate::{
+    Endianess, Error, F2Dot14, Fixed, Fixed16_16, FixedBits, Pod, Primitive, Result, decimal,
+    float, odd, pod, primitive,
+};
+use std::any::type_name;
 use std::io::Write;
 
 macro_rules! write_primitive {
@@ -10,6 +14,7 @@ macro_rules! write_primitive {
         /// It's recommended to use the typed wrappers like `write_u8` instead of this method for cleaner code.
         fn $fn_name<T: Primitive<U, S>, U, const S: usize>(&mut self, data: T) -> Result<()> {
             self.write_all(&data.$write_fn_name())
+                .map_err(|e| Error::for_value(e, None, type_name::<T>(), S))
         }
     };
 }
@@ -61,6 +66,70 @@ macro_rules! write_variable {
     };
 }
 
+macro_rules! write_odd_typed {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $bytes:literal) => {
+        /// Typed wrapper around `write_uint_n` or `write_int_n`.
+        fn $fn_name(&mut self, endianess: Endianess, data: $data_type) -> Result<()> {
+            self.$write_fn($bytes, endianess, data as _)
+        }
+    };
+
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $bytes:literal, $endianess:ident) => {
+        /// Typed wrapper around `write_uint_n` or `write_int_n`.
+        fn $fn_name(&mut self, data: $data_type) -> Result<()> {
+            self.$write_fn($bytes, Endianess::$endianess, data as _)
+        }
+    };
+}
+
+macro_rules! write_float_typed {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path) => {
+        /// Converts a floating point value to another format and writes it.
+        fn $fn_name(&mut self, endianess: Endianess, data: $data_type) -> Result<()> {
+            self.$write_fn(endianess, $convert(data))
+        }
+    };
+
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path, $endianess:ident) => {
+        /// Converts a floating point value to another format and writes it.
+        fn $fn_name(&mut self, data: $data_type) -> Result<()> {
+            self.$write_fn(Endianess::$endianess, $convert(data))
+        }
+    };
+}
+
+macro_rules! write_float_checked {
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path) => {
+        /// Converts a floating point value to another format and writes it, failing if it can't be represented.
+        fn $fn_name(&mut self, endianess: Endianess, data: $data_type) -> Result<()> {
+            self.$write_fn(endianess, $convert(data)?)
+        }
+    };
+
+    ($fn_name:ident, $data_type:ty, $write_fn:ident, $convert:path, $endianess:ident) => {
+        /// Converts a floating point value to another format and writes it, failing if it can't be represented.
+        fn $fn_name(&mut self, data: $data_type) -> Result<()> {
+            self.$write_fn(Endianess::$endianess, $convert(data)?)
+        }
+    };
+}
+
+macro_rules! write_float_bytes {
+    ($fn_name:ident, $convert:path) => {
+        /// Converts a floating point value to another format and writes it.
+        fn $fn_name(&mut self, endianess: Endianess, data: f64) -> Result<()> {
+            self.write_u8_array($convert(data, endianess))
+        }
+    };
+
+    ($fn_name:ident, $convert:path, $endianess:ident) => {
+        /// Converts a floating point value to another format and writes it.
+        fn $fn_name(&mut self, data: f64) -> Result<()> {
+            self.write_u8_array($convert(data, Endianess::$endianess))
+        }
+    };
+}
+
 /// Extension trait for `Write` that provides methods for writeing supported value types.
 ///
 /// **Note:** do not borrow this as `&mut dyn WriteVal`, as this would not compile. Use `&mut dyn Write` instead.
@@ -102,6 +171,23 @@ pub trait WriteVal: Write {
 
     write_primitive_typed!(write_bool, bool, write_ne);
 
+    /// Writes a bool as a 2 byte value of 0 or 1.
+    fn write_bool16(&mut self, endianess: Endianess, data: bool) -> Result<()> {
+        self.write_u16(endianess, data as u16)
+    }
+
+    /// Writes a bool as a 4 byte value of 0 or 1, like the Windows `BOOL`.
+    fn write_bool32(&mut self, endianess: Endianess, data: bool) -> Result<()> {
+        self.write_u32(endianess, data as u32)
+    }
+
+    write_primitive_typed!(write_char, char);
+
+    /// Writes a char encoded as UTF-8, which takes 1 to 4 bytes.
+    fn write_char_utf8(&mut self, data: char) -> Result<()> {
+        primitive::write_utf8_char(self, data)
+    }
+
     write_dynamic_typed!(write_vec, Vec<u8>);
     write_dynamic_typed!(write_str, String);
 
@@ -157,11 +243,179 @@ pub trait WriteVal: Write {
     write_primitive_typed!(write_f64_le, f64, write_le);
     write_primitive_typed!(write_f64_be, f64, write_be);
 
+    write_primitive_typed!(write_char_ne, char, write_ne);
+    write_primitive_typed!(write_char_le, char, write_le);
+    write_primitive_typed!(write_char_be, char, write_be);
+
+    /// Writes an unsigned integer with a width of 1 to 8 bytes, checking that it fits.
+    ///
+    /// It's recommended to use the typed wrappers like `write_u24_le` instead of this method for cleaner code.
+    fn write_uint_n(&mut self, bytes: usize, endianess: Endianess, data: u64) -> Result<()> {
+        odd::write_uint(self, bytes, endianess, data)
+    }
+
+    /// Writes a signed integer with a width of 1 to 8 bytes, checking that it fits.
+    ///
+    /// It's recommended to use the typed wrappers like `write_i24_le` instead of this method for cleaner code.
+    fn write_int_n(&mut self, bytes: usize, endianess: Endianess, data: i64) -> Result<()> {
+        odd::write_int(self, bytes, endianess, data)
+    }
+
+    write_odd_typed!(write_u24, u32, write_uint_n, 3);
+    write_odd_typed!(write_u24_ne, u32, write_uint_n, 3, Native);
+    write_odd_typed!(write_u24_le, u32, write_uint_n, 3, Little);
+    write_odd_typed!(write_u24_be, u32, write_uint_n, 3, Big);
+
+    write_odd_typed!(write_i24, i32, write_int_n, 3);
+    write_odd_typed!(write_i24_ne, i32, write_int_n, 3, Native);
+    write_odd_typed!(write_i24_le, i32, write_int_n, 3, Little);
+    write_odd_typed!(write_i24_be, i32, write_int_n, 3, Big);
+
+    write_odd_typed!(write_u40, u64, write_uint_n, 5);
+    write_odd_typed!(write_u40_ne, u64, write_uint_n, 5, Native);
+    write_odd_typed!(write_u40_le, u64, write_uint_n, 5, Little);
+    write_odd_typed!(write_u40_be, u64, write_uint_n, 5, Big);
+
+    write_odd_typed!(write_i40, i64, write_int_n, 5);
+    write_odd_typed!(write_i40_ne, i64, write_int_n, 5, Native);
+    write_odd_typed!(write_i40_le, i64, write_int_n, 5, Little);
+    write_odd_typed!(write_i40_be, i64, write_int_n, 5, Big);
+
+    write_odd_typed!(write_u48, u64, write_uint_n, 6);
+    write_odd_typed!(write_u48_ne, u64, write_uint_n, 6, Native);
+    write_odd_typed!(write_u48_le, u64, write_uint_n, 6, Little);
+    write_odd_typed!(write_u48_be, u64, write_uint_n, 6, Big);
+
+    write_odd_typed!(write_i48, i64, write_int_n, 6);
+    write_odd_typed!(write_i48_ne, i64, write_int_n, 6, Native);
+    write_odd_typed!(write_i48_le, i64, write_int_n, 6, Little);
+    write_odd_typed!(write_i48_be, i64, write_int_n, 6, Big);
+
+    write_odd_typed!(write_u56, u64, write_uint_n, 7);
+    write_odd_typed!(write_u56_ne, u64, write_uint_n, 7, Native);
+    write_odd_typed!(write_u56_le, u64, write_uint_n, 7, Little);
+    write_odd_typed!(write_u56_be, u64, write_uint_n, 7, Big);
+
+    write_odd_typed!(write_i56, i64, write_int_n, 7);
+    write_odd_typed!(write_i56_ne, i64, write_int_n, 7, Native);
+    write_odd_typed!(write_i56_le, i64, write_int_n, 7, Little);
+    write_odd_typed!(write_i56_be, i64, write_int_n, 7, Big);
+
+    /// Converts `f32` values to IEEE 754 half precision floats and writes them.
+    fn write_f16_slice(&mut self, endianess: Endianess, data: &[f32]) -> Result<()> {
+        float::write_u16_slice(self, endianess, &float::f32_to_f16_slice(data))
+    }
+
+    /// Converts `f32` values to bfloat16 values and writes them.
+    fn write_bf16_slice(&mut self, endianess: Endianess, data: &[f32]) -> Result<()> {
+        float::write_u16_slice(self, endianess, &float::f32_to_bf16_slice(data))
+    }
+
+    write_float_typed!(write_f16, f32, write_u16, float::f32_to_f16);
+    write_float_typed!(write_f16_ne, f32, write_u16, float::f32_to_f16, Native);
+    write_float_typed!(write_f16_le, f32, write_u16, float::f32_to_f16, Little);
+    write_float_typed!(write_f16_be, f32, write_u16, float::f32_to_f16, Big);
+
+    write_float_typed!(write_bf16, f32, write_u16, float::f32_to_bf16);
+    write_float_typed!(write_bf16_ne, f32, write_u16, float::f32_to_bf16, Native);
+    write_float_typed!(write_bf16_le, f32, write_u16, float::f32_to_bf16, Little);
+    write_float_typed!(write_bf16_be, f32, write_u16, float::f32_to_bf16, Big);
+
+    write_float_bytes!(write_f80, float::f80_to_bytes);
+    write_float_bytes!(write_f80_ne, float::f80_to_bytes, Native);
+    write_float_bytes!(write_f80_le, float::f80_to_bytes, Little);
+    write_float_bytes!(write_f80_be, float::f80_to_bytes, Big);
+
+    write_float_checked!(write_ibm32, f64, write_u32, float::f64_to_ibm32);
+    write_float_checked!(write_ibm32_ne, f64, write_u32, float::f64_to_ibm32, Native);
+    write_float_checked!(write_ibm32_le, f64, write_u32, float::f64_to_ibm32, Little);
+    write_float_checked!(write_ibm32_be, f64, write_u32, float::f64_to_ibm32, Big);
+
+    write_float_checked!(write_ibm64, f64, write_u64, float::f64_to_ibm64);
+    write_float_checked!(write_ibm64_ne, f64, write_u64, float::f64_to_ibm64, Native);
+    write_float_checked!(write_ibm64_le, f64, write_u64, float::f64_to_ibm64, Little);
+    write_float_checked!(write_ibm64_be, f64, write_u64, float::f64_to_ibm64, Big);
+
+    /// Converts a value to a VAX F_floating value and writes it, failing if it can't be represented.
+    fn write_vax_f(&mut self, data: f64) -> Result<()> {
+        self.write_u8_array(float::f64_to_vax_f(data)?)
+    }
+
+    /// Converts a value to a VAX D_floating value and writes it, failing if it can't be represented.
+    fn write_vax_d(&mut self, data: f64) -> Result<()> {
+        self.write_u8_array(float::f64_to_vax_d(data)?)
+    }
+
+    /// Converts a value to a VAX G_floating value and writes it, failing if it can't be represented.
+    fn write_vax_g(&mut self, data: f64) -> Result<()> {
+        self.write_u8_array(float::f64_to_vax_g(data)?)
+    }
+
+    /// Writes a fixed-point number with the integer type `T` and `FRAC` fractional bits.
+    ///
+    /// It's recommended to use the typed wrappers like `write_fixed16_16` for common formats.
+    fn write_fixed<T, const FRAC: u32, const S: usize>(
+        &mut self,
+        endianess: Endianess,
+        data: Fixed<T, FRAC>,
+    ) -> Result<()>
+    where
+        T: FixedBits + Primitive<T, S>,
+    {
+        use Endianess::*;
+        match endianess {
+            Little => self.write_le(data),
+            Big => self.write_be(data),
+            Native => self.write_ne(data),
+        }
+    }
+
+    write_primitive_typed!(write_fixed16_16, Fixed16_16);
+    write_primitive_typed!(write_fixed16_16_ne, Fixed16_16, write_ne);
+    write_primitive_typed!(write_fixed16_16_le, Fixed16_16, write_le);
+    write_primitive_typed!(write_fixed16_16_be, Fixed16_16, write_be);
+
+    write_primitive_typed!(write_f2dot14, F2Dot14);
+    write_primitive_typed!(write_f2dot14_ne, F2Dot14, write_ne);
+    write_primitive_typed!(write_f2dot14_le, F2Dot14, write_le);
+    write_primitive_typed!(write_f2dot14_be, F2Dot14, write_be);
+
+    /// Writes a binary-coded decimal field of `len` bytes.
+    ///
+    /// The value is padded with leading zeros.
+    /// Fails if the value has too many digits or the field is longer than 19 bytes.
+    fn write_bcd(&mut self, len: usize, data: u128) -> Result<()> {
+        decimal::write_bcd(self, len, data)
+    }
+
+    /// Writes an IBM packed decimal (COMP-3) field of `len` bytes.
+    ///
+    /// The sign nibble is `C` or `D`.
+    /// Fails if the value has too many digits or the field is longer than 19 bytes.
+    fn write_packed_decimal(&mut self, len: usize, data: i128) -> Result<()> {
+        decimal::write_packed(self, len, data)
+    }
+
     /// Writes a dynamic value to the writeer.
     ///
     /// It's recommended to use the typed wrappers like `write_vec` instead of this method for cleaner code.
     fn write_dynamic<T: crate::Dynamic>(&mut self, data: T) -> Result<()> {
-        self.write_all(data.into_bytes()?)
+        let bytes = data.into_bytes()?;
+        self.write_all(bytes)
+            .map_err(|e| Error::for_value(e, None, type_name::<T>(), bytes.len()))
+    }
+
+    /// Writes a plain-old-data value without encoding its fields.
+    fn write_pod<T: Pod>(&mut self, data: &T) -> Result<()> {
+        self.write_all(pod::bytes_of(data))
+            .map_err(|e| Error::for_value(e, None, type_name::<T>(), size_of::<T>()))
+    }
+
+    /// Writes plain-old-data values without encoding their fields.
+    fn write_pod_slice<T: Pod>(&mut self, data: &[T]) -> Result<()> {
+        let bytes = pod::slice_bytes(data);
+        self.write_all(bytes)
+            .map_err(|e| Error::for_value(e, None, type_name::<[T]>(), bytes.len()))
     }
 }
 
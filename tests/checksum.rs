@@ -0,0 +1,120 @@
+use dh::{
+    Checksum, ChecksumReader, ChecksumWriter,
+    Endianess::*,
+    Error, ReadVal, ReadValAt, WriteVal, WriteValAt,
+    checksum::{Adler32, Algorithm, Crc16, Crc32, Crc64, Fletcher16, Fletcher32},
+};
+use std::io::Cursor;
+
+fn sum<C: Checksum>(mut checksum: C, bytes: &[u8]) -> C::Output {
+    checksum.update(bytes);
+    checksum.value()
+}
+
+#[test]
+fn presets() {
+    let check = b"123456789";
+    assert_eq!(sum(Crc32::ieee(), check), 0xcbf4_3926);
+    assert_eq!(sum(Crc32::castagnoli(), check), 0xe306_9283);
+    assert_eq!(sum(Crc16::arc(), check), 0xbb3d);
+    assert_eq!(sum(Crc16::ccitt_false(), check), 0x29b1);
+    assert_eq!(sum(Crc16::xmodem(), check), 0x31c3);
+    assert_eq!(sum(Crc16::modbus(), check), 0x4b37);
+    assert_eq!(sum(Crc16::kermit(), check), 0x2189);
+    assert_eq!(sum(Crc64::ecma(), check), 0x6c40_df5f_0b49_7347);
+    assert_eq!(sum(Crc64::xz(), check), 0x995d_c9bb_df19_39fa);
+    assert_eq!(sum(Adler32::new(), b"Wikipedia"), 0x11e6_0398);
+    assert_eq!(sum(Adler32::new(), &[0xff; 100_000]), 0x149a_302c);
+}
+
+#[test]
+fn reader() {
+    let mut data = b"IHDR123456789".to_vec();
+    data.extend(0xcbf4_3926u32.to_le_bytes());
+    data.extend(b"end");
+
+    let mut reader = ChecksumReader::new(Cursor::new(&data), Crc32::ieee());
+    reader.stop();
+    assert_eq!(reader.read_vec(4).unwrap(), b"IHDR");
+    reader.start();
+    assert_eq!(reader.read_vec(9).unwrap(), b"123456789");
+    reader.verify_crc32_le().unwrap();
+    assert_eq!(reader.value(), 0xcbf4_3926);
+
+    reader.reset();
+    reader.read_vec(3).unwrap();
+    assert_eq!(reader.value(), sum(Crc32::ieee(), b"end"));
+}
+
+#[test]
+fn mismatch() {
+    let mut reader = ChecksumReader::new(Cursor::new(b"ab\x00\x00\x00\x00"), Crc32::ieee());
+    reader.read_vec(2).unwrap();
+    let err = reader.verify_crc32_le().unwrap_err();
+    assert!(matches!(
+        err,
+        Error::ChecksumMismatch {
+            offset: Some(2),
+            expected: 0,
+            ..
+        }
+    ));
+    assert!(err.to_string().starts_with("Crc32 mismatch, stored 0x0"));
+}
+
+#[test]
+fn writer() {
+    let mut writer = ChecksumWriter::new(Vec::new(), Crc16::modbus());
+    writer.write_vec(b"123456789".to_vec()).unwrap();
+    writer.write_checksum(Little).unwrap();
+    assert_eq!(writer.value(), 0x4b37);
+
+    let data = writer.into_inner();
+    assert_eq!(&data[9..], &[0x37, 0x4b]);
+
+    let mut reader = ChecksumReader::new(Cursor::new(data), Crc16::modbus());
+    reader.read_vec(9).unwrap();
+    reader.verify(Little).unwrap();
+}
+
+#[test]
+fn fletcher() {
+    assert_eq!(sum(Fletcher16::new(), b"abcde"), 0xc8f0);
+    assert_eq!(sum(Fletcher16::new(), b"abcdef"), 0x2057);
+    assert_eq!(sum(Fletcher32::new(), b"abcde"), 0xf04f_c729);
+    assert_eq!(sum(Fletcher32::new(), b"abcdef"), 0x5650_2d2a);
+
+    let mut split = Fletcher32::new();
+    split.update(b"abc");
+    split.update(b"def");
+    assert_eq!(split.value(), 0x5650_2d2a);
+}
+
+#[test]
+fn range() {
+    let mut data = Cursor::new(b"HDR\x00123456789\x00\x00\x00\x00".to_vec());
+    data.set_position(2);
+    assert_eq!(
+        data.checksum_at(4, 9, Algorithm::Crc32).unwrap(),
+        0xcbf4_3926
+    );
+    assert_eq!(
+        data.checksum_at(4, 9, Algorithm::Crc16Xmodem).unwrap(),
+        0x31c3
+    );
+    assert_eq!(data.position(), 2);
+
+    data.write_checksum_at(4, 9, Algorithm::Crc32, 13, Big)
+        .unwrap();
+    assert_eq!(&data.get_ref()[13..], &[0xcb, 0xf4, 0x39, 0x26]);
+    assert_eq!(data.position(), 2);
+
+    let err = data.checksum_at(10, 20, Algorithm::Adler32).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::UnexpectedEof {
+            offset: Some(10),
+            ..
+        }
+    ));
+}
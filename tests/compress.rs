@@ -0,0 +1,304 @@
+use dh::{
+    Error, ReadVal,
+    compress::{
+        DeflateWriter, Format, GzipHeader, GzipReader, GzipWriter, InflateReader, Lz4BlockReader,
+        Lz4BlockWriter, LzssFormat, LzssReader, LzssWriter, PackBitsReader, PackBitsWriter,
+        RleReader, RleWriter, ZlibReader, ZlibWriter,
+    },
+};
+use std::io::{Cursor, Read, Write};
+
+fn hex(text: &str) -> Vec<u8> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn hello() -> Vec<u8> {
+    b"Hello, hello, hello! ".repeat(4)
+}
+
+fn sample(len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| (i.wrapping_mul(2_654_435_761) >> 28) as u8 + (i / 700 % 3) as u8 * 40)
+        .collect()
+}
+
+#[test]
+fn zlib_blocks() {
+    // fixed Huffman codes, a stored block and dynamic Huffman codes
+    let fixed = hex("78daf348cdc9c9d751c840a214153c28130400ab221bd5");
+    assert_eq!(Cursor::new(fixed).read_zlib_block(23).unwrap(), hello());
+
+    let mut stored = hex("7801015400abff");
+    stored.extend(hello());
+    stored.extend(hex("ab221bd5"));
+    assert_eq!(Cursor::new(stored).read_zlib_block(95).unwrap(), hello());
+
+    let dynamic = hex(concat!(
+        "78daedd1871184300c44d15a6d8213e008d8aefea41d157005f00b78a31d296b638c0fb5703ee7defb461d",
+        "5c9dd318735137a79d4b29bdd4ca8552c6183b75724d290b0ddcb378c25803b71d9530d6c05db7268c3570",
+        "ef1a08630ddc7e36c2580317e9360f0d5ca7db2a3470866ed3d0c025ba2d400337e8b6060d9c8d18ea85cb",
+        "1d43ab70d360a816ce250c0dc29581a14d3865317411ce670c3d84ab13436fe1b4c3d055b85030f414aea9",
+        "ef0bdf17be2ffcf1851fc6aa013b",
+    ));
+    let expected: Vec<u8> = (0..400)
+        .flat_map(|i| vec![b'a' + (i * 7 % 26) as u8; 1 + i % 5])
+        .collect();
+    assert_eq!(Cursor::new(dynamic).read_zlib_block(186).unwrap(), expected);
+}
+
+#[test]
+fn gzip_header() {
+    let data = hex(concat!(
+        "1f8b0808d204000002ff612e74787400f348cdc9c9d751c840a214153c2813040008",
+        "48d02354000000",
+    ));
+    let mut reader = GzipReader::new(Cursor::new(&data)).unwrap();
+    assert_eq!(reader.header().mtime, 1234);
+    assert_eq!(reader.header().name.as_deref(), Some(&b"a.txt"[..]));
+    let mut text = Vec::new();
+    reader.read_to_end(&mut text).unwrap();
+    assert_eq!(text, hello());
+
+    let mut corrupt = data.clone();
+    corrupt[data.len() - 8] ^= 1;
+    let mut text = Vec::new();
+    let err = GzipReader::new(Cursor::new(corrupt))
+        .unwrap()
+        .read_to_end(&mut text)
+        .unwrap_err();
+    assert!(matches!(Error::from(err), Error::ChecksumMismatch { .. }));
+}
+
+#[test]
+fn round_trip() {
+    let data = sample(300_000);
+
+    let mut writer = DeflateWriter::new(Vec::new());
+    writer.write_all(&data).unwrap();
+    let compressed = writer.finish().unwrap();
+    assert!(compressed.len() < data.len() / 2);
+    let mut output = Vec::new();
+    InflateReader::new(Cursor::new(compressed))
+        .read_to_end(&mut output)
+        .unwrap();
+    assert_eq!(output, data);
+
+    let mut writer = ZlibWriter::new(Vec::new());
+    writer.write_all(&data).unwrap();
+    let compressed = writer.finish().unwrap();
+    let mut output = Vec::new();
+    ZlibReader::new(Cursor::new(compressed))
+        .unwrap()
+        .read_to_end(&mut output)
+        .unwrap();
+    assert_eq!(output, data);
+
+    let header = GzipHeader {
+        name: Some(b"data.bin".to_vec()),
+        ..GzipHeader::default()
+    };
+    let mut writer = GzipWriter::with_header(Vec::new(), header.clone());
+    writer.write_all(&data).unwrap();
+    let compressed = writer.finish().unwrap();
+    let mut reader = GzipReader::new(Cursor::new(compressed)).unwrap();
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).unwrap();
+    assert_eq!(reader.header(), &header);
+    assert_eq!(output, data);
+}
+
+#[test]
+fn copy_decompressed() {
+    let mut writer = GzipWriter::new(Vec::new());
+    writer.write_all(&hello()).unwrap();
+    let mut data = writer.finish().unwrap();
+    let len = data.len() as u64;
+    data.extend(b"tail");
+
+    let mut reader = Cursor::new(data);
+    let mut output = Vec::new();
+    reader
+        .copy_decompressed(len, Format::Gzip, &mut output)
+        .unwrap();
+    assert_eq!(output, hello());
+    assert_eq!(reader.read_vec(4).unwrap(), b"tail");
+}
+
+#[test]
+fn decompression_limit() {
+    let mut writer = ZlibWriter::new(Vec::new());
+    writer.write_all(&[0; 1_000_000]).unwrap();
+    let bomb = writer.finish().unwrap();
+    let len = bomb.len() as u64;
+    assert!(len < 10_000);
+
+    let err = Cursor::new(&bomb)
+        .read_zlib_block_with_limit(len, 1000)
+        .unwrap_err();
+    assert!(matches!(err, Error::LimitExceeded { limit: 1000, .. }));
+    let data = Cursor::new(&bomb)
+        .read_zlib_block_with_limit(len, 1_000_000)
+        .unwrap();
+    assert_eq!(data.len(), 1_000_000);
+
+    let mut writer = GzipWriter::new(Vec::new());
+    writer.write_all(&sample(50_000)).unwrap();
+    let compressed = writer.finish().unwrap();
+    let reader = GzipReader::with_limit(Cursor::new(&compressed), 49_999).unwrap();
+    assert!(read_all(reader).is_err());
+    let reader = InflateReader::with_limit(Cursor::new(&bomb[2..]), 1000);
+    assert!(read_all(reader).is_err());
+}
+
+#[test]
+fn decode_ahead_error() {
+    // two stored blocks, "abc" and the final "def"
+    let data = hex("000300fcff616263010300fcff646566");
+
+    let mut reader = InflateReader::with_limit(Cursor::new(&data), 3);
+    let mut buf = [0; 3];
+    assert_eq!(reader.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"abc");
+    let err = reader.read(&mut buf).unwrap_err();
+    assert!(matches!(
+        Error::from(err),
+        Error::LimitExceeded { limit: 3, .. }
+    ));
+
+    let mut reader = InflateReader::new(Cursor::new(&data[..12]));
+    assert_eq!(reader.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"abc");
+    assert!(reader.read(&mut buf).is_err());
+}
+
+#[test]
+fn trailer_verified_at_end() {
+    let mut writer = ZlibWriter::new(Vec::new());
+    writer.write_all(&hello()).unwrap();
+    let mut data = writer.finish().unwrap();
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    let mut reader = ZlibReader::new(Cursor::new(&data)).unwrap();
+    let err = reader.read_vec(hello().len()).unwrap_err();
+    assert!(matches!(err, Error::ChecksumMismatch { .. }));
+
+    let mut writer = GzipWriter::new(Vec::new());
+    writer.write_all(&hello()).unwrap();
+    let mut data = writer.finish().unwrap();
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    let mut reader = GzipReader::new(Cursor::new(&data)).unwrap();
+    assert!(reader.read_vec(hello().len()).is_err());
+}
+
+#[test]
+fn gzip_extra_too_long() {
+    let header = GzipHeader {
+        extra: Some(vec![0; 65536]),
+        ..GzipHeader::default()
+    };
+    let mut writer = GzipWriter::with_header(Vec::new(), header);
+    let err = writer.write_all(&hello()).unwrap_err();
+    assert!(matches!(
+        Error::from(err),
+        Error::OutOfRange {
+            value: 65536,
+            type_name: "u16",
+            ..
+        }
+    ));
+    assert!(writer.finish().is_err());
+}
+
+fn read_all(mut reader: impl Read) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+#[test]
+fn packbits() {
+    // example from Apple's technical note TN1023
+    let packed = hex("fe aa 02 80 00 2a fd aa 03 80 00 2a 22 f7 aa"
+        .replace(' ', "")
+        .as_str());
+    let unpacked = hex("aaaaaa80002aaaaaaaaa80002a22aaaaaaaaaaaaaaaaaaaa");
+    assert_eq!(
+        read_all(PackBitsReader::new(Cursor::new(packed))).unwrap(),
+        unpacked
+    );
+
+    let data = sample(10_000);
+    let mut writer = PackBitsWriter::new(Vec::new());
+    writer.write_all(&data).unwrap();
+    writer.write_all(&[7; 1000]).unwrap();
+    let packed = writer.finish().unwrap();
+    let unpacked = read_all(PackBitsReader::new(Cursor::new(packed))).unwrap();
+    assert_eq!(&unpacked[..10_000], &data[..]);
+    assert_eq!(&unpacked[10_000..], &[7; 1000]);
+}
+
+#[test]
+fn rle() {
+    let mut data = sample(5000);
+    data.extend([0; 600]);
+
+    let mut writer = RleWriter::new(Vec::new());
+    writer.write_all(&data).unwrap();
+    let encoded = writer.finish().unwrap();
+    assert_eq!(
+        read_all(RleReader::new(Cursor::new(&encoded))).unwrap(),
+        data
+    );
+
+    let err = read_all(RleReader::with_limit(Cursor::new(&encoded), 5000)).unwrap_err();
+    assert!(matches!(
+        Error::from(err),
+        Error::LimitExceeded { limit: 5000, .. }
+    ));
+}
+
+#[test]
+fn lz4_block() {
+    let block = hex("8748656c6c6f2c206807002f2120150027506c6c6f2120");
+    let reader = Lz4BlockReader::new(Cursor::new(block));
+    assert_eq!(read_all(reader).unwrap(), hello());
+
+    let data = sample(200_000);
+    let mut writer = Lz4BlockWriter::new(Vec::new());
+    writer.write_all(&data).unwrap();
+    let compressed = writer.finish().unwrap();
+    assert!(compressed.len() < data.len() / 2);
+    let reader = Lz4BlockReader::with_limit(Cursor::new(&compressed), 200_000);
+    assert_eq!(read_all(reader).unwrap(), data);
+
+    let reader = Lz4BlockReader::with_limit(Cursor::new(&compressed), 1000);
+    assert!(read_all(reader).is_err());
+}
+
+#[test]
+fn lzss() {
+    // a literal, a match overlapping it and a match into the prefilled spaces
+    let okumura = hex("0161eef20000");
+    let reader = LzssReader::new(Cursor::new(okumura), LzssFormat::OKUMURA);
+    assert_eq!(read_all(reader).unwrap(), b"aaaaaa   ");
+
+    let lz10 = hex("40612000");
+    let reader = LzssReader::new(Cursor::new(lz10), LzssFormat::LZ10);
+    assert_eq!(read_all(reader).unwrap(), b"aaaaaa");
+    let reader = LzssReader::new(Cursor::new(hex("800000")), LzssFormat::LZ10);
+    assert!(read_all(reader).is_err());
+
+    let data = sample(50_000);
+    for format in [LzssFormat::OKUMURA, LzssFormat::LZ10] {
+        let mut writer = LzssWriter::new(Vec::new(), format);
+        writer.write_all(&data).unwrap();
+        let compressed = writer.finish().unwrap();
+        assert!(compressed.len() < data.len());
+        let reader = LzssReader::new(Cursor::new(compressed), format);
+        assert_eq!(read_all(reader).unwrap(), data);
+    }
+}
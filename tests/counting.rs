@@ -0,0 +1,81 @@
+use dh::{CountingReader, CountingWriter, ReadVal, ReadValAt, WriteVal};
+use std::io::{Seek, SeekFrom};
+
+#[test]
+fn counting_reader_position() {
+    let data = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    let mut reader = CountingReader::new(&data[..]);
+
+    assert_eq!(reader.read_u8().unwrap(), 0);
+    assert_eq!(reader.read_u16_be().unwrap(), 0x0102);
+    assert_eq!(reader.position(), 3);
+    assert_eq!(reader.stream_position().unwrap(), 3);
+}
+
+#[test]
+fn counting_reader_skip() {
+    let data = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    let mut reader = CountingReader::new(&data[..]);
+
+    reader.seek(SeekFrom::Current(5)).unwrap();
+    assert_eq!(reader.read_u8().unwrap(), 5);
+    reader.seek(SeekFrom::Start(7)).unwrap();
+    assert_eq!(reader.read_u8().unwrap(), 7);
+
+    // overflow
+    assert!(reader.read_u8().is_err());
+}
+
+#[test]
+fn counting_reader_read_at() {
+    let data = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    let mut reader = CountingReader::new(&data[..]);
+
+    assert_eq!(reader.read_u8().unwrap(), 0);
+    assert_eq!(reader.read_u16_le_at(6).unwrap(), 0x0706);
+    assert_eq!(reader.position(), 1);
+    assert_eq!(reader.read_u8_array::<5>().unwrap(), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn counting_reader_window() {
+    let data: Vec<u8> = (0..32).collect();
+    let mut reader = CountingReader::with_window(&data[..], 8);
+
+    assert_eq!(reader.read_u32_le_at(6).unwrap(), 0x09080706);
+    assert_eq!(reader.read_u8().unwrap(), 0);
+
+    reader.seek(SeekFrom::Start(20)).unwrap();
+    assert_eq!(reader.read_u8_at(24).unwrap(), 24);
+    assert!(reader.seek(SeekFrom::Start(8)).is_err());
+    assert!(reader.seek(SeekFrom::End(0)).is_err());
+}
+
+#[test]
+fn counting_reader_skip_past_window() {
+    let data: Vec<u8> = (0..20000).map(|i| i as u8).collect();
+    let mut reader = CountingReader::new(&data[..]);
+
+    reader.seek(SeekFrom::Start(10000)).unwrap();
+    assert_eq!(reader.read_u8().unwrap(), (10000 % 256) as u8);
+    reader.seek(SeekFrom::Current(9000)).unwrap();
+    assert_eq!(reader.read_u8().unwrap(), (19001 % 256) as u8);
+    assert_eq!(reader.position(), 19002);
+
+    // the position before the skip is gone, so reading ahead can't return to it
+    let mut reader = CountingReader::with_window(&data[..], 8);
+    assert!(reader.read_u8_at(16).is_err());
+}
+
+#[test]
+fn counting_writer() {
+    let mut writer = CountingWriter::new(Vec::new());
+
+    writer.write_u16_le(0x0201).unwrap();
+    writer.seek(SeekFrom::Start(4)).unwrap();
+    writer.write_u8(3).unwrap();
+    assert_eq!(writer.position(), 5);
+    assert!(writer.seek(SeekFrom::Start(2)).is_err());
+
+    assert_eq!(writer.into_inner(), [1, 2, 0, 0, 3]);
+}
@@ -0,0 +1,80 @@
+use dh::{Error, ReadVal, ReadValAt, WriteVal, WriteValAt};
+use std::io::{Cursor, ErrorKind};
+
+#[test]
+fn bcd() {
+    let mut cursor = Cursor::new(vec![0x20, 0x26, 0x10, 0x18, 0x00, 0x07, 0x1a]);
+
+    assert_eq!(cursor.read_bcd(2).unwrap(), 2026);
+    assert_eq!(cursor.read_bcd(1).unwrap(), 10);
+    assert_eq!(cursor.read_bcd_str(3).unwrap(), "180007");
+    assert_eq!(cursor.read_bcd_str_at(4, 2).unwrap(), "7");
+    assert_eq!(cursor.read_bcd_at(0, 1).unwrap(), 20);
+
+    assert!(matches!(
+        cursor.read_bcd_at(6, 1),
+        Err(Error::InvalidEncoding {
+            offset: Some(6),
+            type_name: "bcd",
+            ..
+        })
+    ));
+    assert_eq!(
+        cursor.read_bcd_at(0, 20).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_bcd(3, 2026).unwrap();
+    cursor.write_bcd_at(0, 1, 9).unwrap();
+    assert_eq!(cursor.get_ref(), &[0x09, 0x20, 0x26]);
+    assert!(matches!(
+        cursor.write_bcd(1, 100),
+        Err(Error::OutOfRange { value: 100, .. })
+    ));
+}
+
+#[test]
+fn packed_decimal() {
+    let data = [
+        0x12, 0x34, 0x5c, 0x00, 0x12, 0x0d, 0x0f, 0x00, 0x0b, 0x12, 0x3e,
+    ];
+    let mut cursor = Cursor::new(data.to_vec());
+
+    assert_eq!(cursor.read_packed_decimal(3).unwrap(), 12345);
+    assert_eq!(cursor.read_packed_decimal(3).unwrap(), -120);
+    assert_eq!(cursor.read_packed_decimal(1).unwrap(), 0);
+    assert_eq!(cursor.read_packed_decimal_str(2).unwrap(), "0");
+    assert_eq!(cursor.read_packed_decimal_str(2).unwrap(), "123");
+    assert_eq!(cursor.read_packed_decimal_str_at(3, 3).unwrap(), "-120");
+    assert_eq!(cursor.read_packed_decimal_at(9, 2).unwrap(), 123);
+
+    assert!(matches!(
+        cursor.read_packed_decimal_at(0, 2),
+        Err(Error::InvalidEncoding {
+            reason: "invalid sign nibble",
+            ..
+        })
+    ));
+    assert!(matches!(
+        cursor.read_packed_decimal_at(5, 2),
+        Err(Error::InvalidEncoding {
+            offset: Some(5),
+            reason: "invalid digit nibble",
+            ..
+        })
+    ));
+
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_packed_decimal(3, 12345).unwrap();
+    cursor.write_packed_decimal(2, -120).unwrap();
+    cursor.write_packed_decimal_at(0, 1, 7).unwrap();
+    assert_eq!(cursor.get_ref(), &[0x7c, 0x34, 0x5c, 0x12, 0x0d]);
+    assert!(cursor.write_packed_decimal(2, -1000).is_err());
+
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_packed_decimal(19, i128::MIN + 1).unwrap_err();
+    cursor.write_packed_decimal(19, -(10i128.pow(36))).unwrap();
+    cursor.set_position(0);
+    assert_eq!(cursor.read_packed_decimal(19).unwrap(), -(10i128.pow(36)));
+}
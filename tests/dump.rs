@@ -0,0 +1,65 @@
+use dh::{HexDump, ReadValAt, Span};
+use std::io::Cursor;
+
+#[test]
+fn styles() {
+    let data = b"Hello, world!\nHe";
+    assert_eq!(
+        HexDump::xxd().to_string(&data[..14]),
+        "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a       Hello, world!.\n"
+    );
+    assert_eq!(
+        HexDump::canonical().to_string(data),
+        concat!(
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 48 65  |Hello, world!.He|\n",
+            "00000010\n"
+        )
+    );
+    assert_eq!(
+        HexDump::canonical()
+            .with_width(4)
+            .with_group(2)
+            .with_uppercase(true)
+            .to_string(&[0xab, 0, 0x41, 0x7f, 0x20]),
+        "00000000  AB 00  41 7F  |..A.|\n00000004  20            | |\n00000005\n"
+    );
+    assert_eq!(HexDump::canonical().to_string(&[]), "");
+}
+
+#[test]
+fn range() {
+    let mut data = Cursor::new((0..64).collect::<Vec<u8>>());
+    let mut text = Vec::new();
+    data.hexdump_at(0x22, 3, &HexDump::xxd().with_width(8), &mut text)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(text).unwrap(),
+        "00000022: 2223 24              \"#$\n"
+    );
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn spans() {
+    let mut data = Cursor::new(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec());
+    let spans = [
+        Span::new(12, 4, "type").with_value("\"IHDR\""),
+        Span::new(0, 4, "magic"),
+        Span::new(8, 4, "length").with_value(13),
+    ];
+    let mut text = Vec::new();
+    HexDump::canonical()
+        .with_width(8)
+        .dump_spans(&mut data, &spans, &mut text)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(text).unwrap(),
+        concat!(
+            "00000000  89 50 4e 47              |.PNG    |  magic\n",
+            "00000004  0d 0a 1a 0a              |....|\n",
+            "00000008  00 00 00 0d              |....    |  length: 13\n",
+            "0000000c  49 48 44 52              |IHDR    |  type: \"IHDR\"\n",
+        )
+    );
+    assert_eq!(data.position(), 0);
+}
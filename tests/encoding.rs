@@ -0,0 +1,74 @@
+use dh::{
+    Error, ReadVal, WriteVal,
+    encoding::{Decoder, Encoder, Encoding},
+};
+use std::io::Cursor;
+
+#[test]
+fn rfc4648_vectors() {
+    let inputs = ["", "f", "fo", "foo", "foob", "fooba", "foobar"];
+    let base64 = [
+        "", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy",
+    ];
+    let base32 = [
+        "",
+        "MY======",
+        "MZXQ====",
+        "MZXW6===",
+        "MZXW6YQ=",
+        "MZXW6YTB",
+        "MZXW6YTBOI======",
+    ];
+    for ((input, base64), base32) in inputs.iter().zip(base64).zip(base32) {
+        assert_eq!(Encoding::BASE64.encode(input.as_bytes()), base64);
+        assert_eq!(Encoding::BASE64.decode(base64).unwrap(), input.as_bytes());
+        assert_eq!(Encoding::BASE32.encode(input.as_bytes()), base32);
+        assert_eq!(Encoding::BASE32.decode(base32).unwrap(), input.as_bytes());
+    }
+
+    let unpadded = Encoding::BASE64.with_padding(false);
+    assert_eq!(unpadded.encode(b"fo"), "Zm8");
+    assert_eq!(unpadded.decode("Zm8").unwrap(), b"fo");
+    assert_eq!(Encoding::BASE64_URL.encode(&[0xfb, 0xff]), "-_8=");
+    assert_eq!(Encoding::BASE64.encode(&[0xfb, 0xff]), "+/8=");
+}
+
+#[test]
+fn hex() {
+    assert_eq!(Encoding::HEX_LOWER.encode(&[0xde, 0xad, 0x01]), "dead01");
+    assert_eq!(Encoding::HEX_UPPER.encode(&[0xde, 0xad, 0x01]), "DEAD01");
+    assert_eq!(
+        Encoding::HEX_LOWER.decode("DE ad\n01").unwrap(),
+        [0xde, 0xad, 0x01]
+    );
+    assert!(Encoding::HEX_LOWER.decode("abc").is_err());
+
+    let err = Encoding::HEX_LOWER.decode("zz").unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidEncoding {
+            type_name: "hex",
+            reason: "invalid character",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn streams() {
+    let mut writer = Encoder::new(Vec::new(), Encoding::BASE64);
+    writer.write_u32_le(42).unwrap();
+    writer.write_u16_be(0xbeef).unwrap();
+    let text = writer.finish().unwrap();
+    assert_eq!(text, b"KgAAAL7v");
+
+    let mut reader = Decoder::new(Cursor::new(text), Encoding::BASE64);
+    assert_eq!(reader.read_u32_le().unwrap(), 42);
+    assert_eq!(reader.read_u16_be().unwrap(), 0xbeef);
+    assert!(reader.read_u8().is_err());
+
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i * 7 % 256) as u8).collect();
+    let text = Encoding::BASE32.encode(&data);
+    let mut reader = Decoder::new(Cursor::new(text), Encoding::BASE32);
+    assert_eq!(reader.read_vec(10_000).unwrap(), data);
+}
@@ -0,0 +1,40 @@
+use dh::{Endian, Endianess, ReadVal, ReadValAt, WriteVal};
+use std::io::Cursor;
+
+#[test]
+fn endian_read() {
+    let data = [b'M', b'M', 0, 42, 0, 0, 0, 8, b'I', b'I', 42, 0];
+    let mut reader = Endian::new(Cursor::new(data), Endianess::Little);
+
+    let marker = reader.read_u8_array::<2>().unwrap();
+    if marker == *b"MM" {
+        reader.set_endianess(Endianess::Big);
+    }
+    assert_eq!(reader.endianess(), Endianess::Big);
+    assert_eq!(reader.read_u16().unwrap(), 42);
+    assert_eq!(reader.read_u32().unwrap(), 8);
+
+    reader.set_endianess(Endianess::Little);
+    assert_eq!(reader.read_u16_at(10).unwrap(), 42);
+    assert_eq!(reader.read_u16_be_at(8).unwrap(), 0x4949);
+    assert_eq!(
+        ReadVal::read_u16(&mut reader, Endianess::Big).unwrap(),
+        0x4949
+    );
+}
+
+#[test]
+fn endian_write() {
+    let mut writer = Endian::new(Cursor::new(vec![0u8; 4]), Endianess::Big);
+
+    writer.write_u16(0x0102).unwrap();
+    writer.write_u16_le(0x0304).unwrap();
+    writer.set_endianess(Endianess::Little);
+    writer.write_i32(-2).unwrap();
+    writer.write_u16_at(0, 0x0506).unwrap();
+
+    assert_eq!(
+        writer.into_inner().into_inner(),
+        [6, 5, 4, 3, 0xfe, 0xff, 0xff, 0xff]
+    );
+}
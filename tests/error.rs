@@ -0,0 +1,132 @@
+use dh::{
+    CountingReader, CountingWriter, Error, PeekVal, ReadVal, ReadValAt, ResultExt, WriteVal,
+    WriteValAt,
+};
+use std::io::{Cursor, ErrorKind};
+
+#[test]
+fn error_eof() {
+    let mut cursor = Cursor::new([0u8; 4]);
+
+    let err = cursor.read_u32_le_at(2).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::UnexpectedEof {
+            offset: Some(2),
+            type_name: "u32",
+            size: 4
+        }
+    ));
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(
+        err.to_string(),
+        "Unexpected end of stream while reading u32 (4 bytes) at offset 0x2"
+    );
+
+    cursor.set_position(0);
+    let err = cursor.peek_u64_be().unwrap_err();
+    assert_eq!(err.offset(), Some(0));
+
+    let err = cursor.read_vec(8).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn error_sequential_offset() {
+    let data = [0u8; 6];
+    let mut reader = CountingReader::new(&data[..]);
+    reader.read_u16_le().unwrap();
+    let err = reader.read_u64_be().unwrap_err();
+    assert!(matches!(
+        err,
+        Error::UnexpectedEof {
+            offset: Some(2),
+            type_name: "u64",
+            size: 8
+        }
+    ));
+    assert_eq!(reader.read_vec(8).unwrap_err().offset(), Some(6));
+
+    let mut cursor = Cursor::new(data);
+    cursor.set_position(4);
+    let err = cursor
+        .read_positioned(|r| r.read_u32_le())
+        .context("count")
+        .unwrap_err();
+    assert_eq!(err.offset(), Some(4));
+    assert_eq!(err.path(), "count");
+
+    let mut buf = [0u8; 4];
+    let mut writer = CountingWriter::new(&mut buf[..]);
+    writer.write_u16_le(1).unwrap();
+    let err = writer.write_u32_le(2).unwrap_err();
+    assert_eq!(err.offset(), Some(2));
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+
+    let mut cursor = Cursor::new([0u8; 4]);
+    cursor.set_position(3);
+    let err = cursor.write_positioned(|w| w.write_u16_be(1)).unwrap_err();
+    assert_eq!(err.offset(), Some(3));
+}
+
+#[test]
+fn error_utf8() {
+    let mut cursor = Cursor::new([b'a', 0xff, b'b', b'c']);
+
+    let err = cursor.read_str_at(1, 2).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InvalidUtf8 {
+            offset: Some(1),
+            len: 2
+        }
+    ));
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn error_write() {
+    let mut cursor = Cursor::new([0u8; 4]);
+
+    let err = cursor.write_u32_be_at(2, 0).unwrap_err();
+    assert_eq!(err.offset(), Some(2));
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+}
+
+#[test]
+fn error_context() {
+    let mut cursor = Cursor::new([0u8; 4]);
+
+    let err = cursor
+        .read_u64_le_at(0)
+        .context("name")
+        .context("[3]")
+        .context("entries")
+        .with_context(|| "header")
+        .unwrap_err();
+    assert_eq!(err.labels(), ["header", "entries", "[3]", "name"]);
+    assert_eq!(err.path(), "header.entries[3].name");
+    assert_eq!(err.offset(), Some(0));
+    assert!(matches!(err.root(), Error::UnexpectedEof { size: 8, .. }));
+    assert_eq!(
+        err.to_string(),
+        "header.entries[3].name: Unexpected end of stream while reading u64 (8 bytes) at offset 0x0"
+    );
+}
+
+#[test]
+fn error_io_conversion() {
+    let mut cursor = Cursor::new([0u8; 2]);
+
+    let err = cursor.read_u32_be_at(1).context("size").unwrap_err();
+    let io_err = std::io::Error::from(err);
+    assert_eq!(io_err.kind(), ErrorKind::UnexpectedEof);
+
+    let err = Error::from(io_err);
+    assert_eq!(err.path(), "size");
+    assert_eq!(err.offset(), Some(1));
+
+    let err = Error::from(std::io::Error::from(ErrorKind::BrokenPipe));
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(std::io::Error::from(err).kind(), ErrorKind::BrokenPipe);
+}
@@ -0,0 +1,71 @@
+use dh::{
+    Endianess::*, Error, F2Dot14, Fixed, Fixed16_16, Q15, ReadVal, ReadValAt, UFixed16_16,
+    WriteVal, WriteValAt,
+};
+use std::io::Cursor;
+
+#[test]
+fn conversion() {
+    assert_eq!(Fixed16_16::from_bits(0x0001_8000).to_f64(), 1.5);
+    assert_eq!(Fixed16_16::from_bits(-0x0001_8000).to_f64(), -1.5);
+    assert_eq!(F2Dot14::from_bits(0x7fff).to_f64(), 2.0 - 2f64.powi(-14));
+    assert_eq!(F2Dot14::from_bits(-0x8000).to_f64(), -2.0);
+    assert_eq!(UFixed16_16::from_bits(0xac44_0000).to_f64(), 44100.0);
+
+    assert_eq!(F2Dot14::from_f64(0.5).unwrap().to_bits(), 0x2000);
+    assert_eq!(Q15::from_f64(-1.0).unwrap().to_bits(), i16::MIN);
+    // 2.5 and 3.5 ulps are rounded to even
+    assert_eq!(Fixed::<u8, 1>::from_f64(1.25).unwrap().to_bits(), 2);
+    assert_eq!(Fixed::<u8, 1>::from_f64(1.75).unwrap().to_bits(), 4);
+
+    assert!(matches!(
+        Q15::from_f64(1.0),
+        Err(Error::Unrepresentable { value: 1.0, .. })
+    ));
+    assert!(UFixed16_16::from_f64(-0.5).is_err());
+    assert!(Fixed::<u64, 0>::from_f64(2f64.powi(64)).is_err());
+    assert!(Fixed16_16::from_f64(f64::NAN).is_err());
+
+    for bits in [i32::MIN, -1, 0, 1, 0x1234_5678, i32::MAX] {
+        let value = Fixed16_16::from_bits(bits);
+        assert_eq!(Fixed16_16::from_f64(value.to_f64()).unwrap(), value);
+    }
+
+    assert_eq!(format!("{:?}", F2Dot14::from_bits(0x6000)), "Fixed(1.5)");
+    assert_eq!(Fixed16_16::from_bits(0x0002_4000).to_string(), "2.25");
+}
+
+#[test]
+fn read_write() {
+    let data = [0x00, 0x01, 0x80, 0x00, 0x00, 0x60, 0x80, 0x7f];
+    let mut cursor = Cursor::new(data.to_vec());
+
+    assert_eq!(cursor.read_fixed16_16_be().unwrap().to_f64(), 1.5);
+    assert_eq!(cursor.read_f2dot14(Little).unwrap().to_f64(), 1.5);
+    assert_eq!(cursor.read_fixed::<u8, 4, 1>(Big).unwrap().to_f64(), 8.0);
+    let value: Fixed<i8, 7> = cursor.read_fixed(Little).unwrap();
+    assert_eq!(value.to_bits(), 0x7f);
+    assert_eq!(cursor.read_f2dot14_le_at(4).unwrap().to_f64(), 1.5);
+
+    // round-trips keep the bits
+    let mut copy = Cursor::new(Vec::new());
+    cursor.set_position(0);
+    copy.write_fixed16_16_be(cursor.read_fixed16_16_be().unwrap())
+        .unwrap();
+    copy.write_f2dot14_le(cursor.read_f2dot14_le().unwrap())
+        .unwrap();
+    copy.write_fixed(Big, Fixed::<u8, 4>::from_bits(0x80))
+        .unwrap();
+    copy.write_fixed(Native, Fixed::<i8, 7>::from_f64(127.0 / 128.0).unwrap())
+        .unwrap();
+    assert_eq!(copy.get_ref(), &data);
+
+    copy.write_f2dot14_at(0, Big, F2Dot14::from_f64(-2.0).unwrap())
+        .unwrap();
+    copy.write_fixed_at(6, Little, Fixed::<i8, 7>::from_bits(0))
+        .unwrap();
+    assert_eq!(
+        copy.into_inner(),
+        [0x80, 0x00, 0x80, 0x00, 0x00, 0x60, 0x00, 0x7f]
+    );
+}
@@ -0,0 +1,95 @@
+use dh::{Endianess::*, Error, Flags, ReadVal, ReadValAt};
+use std::io::Cursor;
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    File,
+    Directory,
+}
+
+impl TryFrom<u16> for Kind {
+    type Error = ();
+
+    fn try_from(value: u16) -> Result<Self, ()> {
+        match value {
+            1 => Ok(Kind::File),
+            2 => Ok(Kind::Directory),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Permissions(u8);
+
+impl Permissions {
+    const READ: u8 = 1;
+    const WRITE: u8 = 2;
+}
+
+impl Flags for Permissions {
+    type Bits = u8;
+    const ALL: u8 = Self::READ | Self::WRITE;
+
+    fn from_bits_retain(bits: u8) -> Self {
+        Self(bits)
+    }
+}
+
+#[test]
+fn enums() {
+    let mut cursor = Cursor::new([0x00, 0x01, 0x02, 0x00, 0x07, 0x00]);
+
+    assert_eq!(cursor.read_enum::<Kind, u16, 2>(Big).unwrap(), Kind::File);
+    assert_eq!(
+        cursor.read_enum::<Kind, u16, 2>(Little).unwrap(),
+        Kind::Directory
+    );
+    assert!(matches!(
+        cursor.read_enum::<Kind, u16, 2>(Little),
+        Err(Error::UnknownDiscriminant { value: 7, .. })
+    ));
+    assert_eq!(
+        cursor.read_enum_at::<Kind, u16, 2>(2, Little).unwrap(),
+        Kind::Directory
+    );
+
+    let error = cursor.read_enum_at::<Kind, u16, 2>(1, Big).unwrap_err();
+    assert_eq!(error.offset(), Some(1));
+    assert_eq!(
+        error.to_string(),
+        "Unknown discriminant 258 for flags::Kind at offset 0x1"
+    );
+}
+
+#[test]
+fn flags() {
+    let mut cursor = Cursor::new([0x03, 0x05]);
+
+    assert_eq!(
+        cursor.read_flags::<Permissions, 1>(Native).unwrap(),
+        Permissions(3)
+    );
+    assert!(matches!(
+        cursor.read_flags::<Permissions, 1>(Native),
+        Err(Error::UnknownFlags { bits: 4, .. })
+    ));
+    assert_eq!(
+        cursor
+            .read_flags_retain_at::<Permissions, 1>(1, Native)
+            .unwrap(),
+        Permissions(5)
+    );
+    assert_eq!(
+        cursor
+            .read_flags_at::<Permissions, 1>(1, Native)
+            .unwrap_err()
+            .offset(),
+        Some(1)
+    );
+    cursor.set_position(1);
+    assert_eq!(
+        cursor.read_flags_retain::<Permissions, 1>(Big).unwrap(),
+        Permissions(5)
+    );
+}
@@ -0,0 +1,220 @@
+use dh::float::{
+    bf16_to_f32, f16_to_f32, f32_to_bf16, f32_to_f16, f64_to_ibm32, f64_to_ibm64, f64_to_vax_d,
+    f64_to_vax_f, f64_to_vax_g, f64_to_x87, ibm32_to_f64, ibm64_to_f64, vax_d_to_f64, vax_f_to_f64,
+    vax_g_to_f64, x87_to_f64,
+};
+use dh::{Endianess::*, Error, ReadVal, ReadValAt, WriteVal, WriteValAt};
+use std::io::Cursor;
+
+#[test]
+fn f16_conversion() {
+    assert_eq!(f16_to_f32(0x3c00), 1.0);
+    assert_eq!(f16_to_f32(0xc000), -2.0);
+    assert_eq!(f16_to_f32(0x7bff), 65504.0);
+    assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+    assert_eq!(f16_to_f32(0x8000).to_bits(), 0x8000_0000);
+    assert_eq!(f16_to_f32(0xfc00), f32::NEG_INFINITY);
+    assert_eq!(f16_to_f32(0x7d23).to_bits(), 0x7fa4_6000);
+
+    assert_eq!(f32_to_f16(65519.0), 0x7bff);
+    assert_eq!(f32_to_f16(65520.0), 0x7c00);
+    assert_eq!(f32_to_f16(1e10), 0x7c00);
+    assert_eq!(f32_to_f16(1.0 + 2f32.powi(-11)), 0x3c00);
+    assert_eq!(f32_to_f16(1.0 + 3.0 * 2f32.powi(-11)), 0x3c02);
+    assert_eq!(f32_to_f16(2f32.powi(-25)), 0x0000);
+    assert_eq!(f32_to_f16(1.5 * 2f32.powi(-25)), 0x0001);
+    assert_eq!(f32_to_f16(-1e-10), 0x8000);
+    assert_eq!(f32_to_f16(f32::from_bits(0x7f80_0001)), 0x7e00);
+    assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+
+    for bits in 0..=u16::MAX {
+        let value = f16_to_f32(bits);
+        match value.is_nan() {
+            true => assert!(f16_to_f32(f32_to_f16(value)).is_nan()),
+            false => assert_eq!(f32_to_f16(value), bits),
+        }
+    }
+}
+
+#[test]
+fn bf16_conversion() {
+    assert_eq!(bf16_to_f32(0x3f80), 1.0);
+    assert_eq!(bf16_to_f32(0xff80), f32::NEG_INFINITY);
+
+    assert_eq!(f32_to_bf16(1.0), 0x3f80);
+    assert_eq!(f32_to_bf16(f32::from_bits(0x3f80_8000)), 0x3f80);
+    assert_eq!(f32_to_bf16(f32::from_bits(0x3f81_8000)), 0x3f82);
+    assert_eq!(f32_to_bf16(f32::MAX), 0x7f80);
+    assert_eq!(f32_to_bf16(f32::from_bits(0x7f80_0001)), 0x7fc0);
+    assert_eq!(f32_to_bf16(f32::from_bits(0x0000_8001)), 0x0001);
+}
+
+#[test]
+fn read_half() {
+    let data = [0x00u8, 0x3c, 0xc0, 0x00, 0x80, 0x3f, 0x3f, 0x40];
+    let mut cursor = Cursor::new(data);
+
+    assert_eq!(cursor.read_f16_le().unwrap(), 1.0);
+    assert_eq!(cursor.read_f16(Big).unwrap(), -2.0);
+    assert_eq!(cursor.read_bf16_le().unwrap(), 1.0);
+    assert_eq!(cursor.read_bf16_be().unwrap(), 0.75);
+    assert_eq!(cursor.read_bf16_le_at(4).unwrap(), 1.0);
+
+    cursor.set_position(0);
+    assert_eq!(
+        cursor.read_f16_vec(2, Little).unwrap(),
+        [1.0, 3.0 * 2f32.powi(-18)]
+    );
+
+    // overflow
+    assert!(cursor.read_bf16_vec(3, Little).is_err());
+}
+
+#[test]
+fn write_half() {
+    let mut cursor = Cursor::new(Vec::new());
+
+    cursor.write_f16_be(1.0).unwrap();
+    cursor.write_bf16(Little, -2.0).unwrap();
+    cursor.write_f16_slice(Little, &[1.0, 65504.0]).unwrap();
+    cursor.write_bf16_slice(Big, &[1.0]).unwrap();
+    cursor.write_f16_le_at(0, 0.5).unwrap();
+
+    assert_eq!(
+        cursor.into_inner(),
+        [0x00, 0x38, 0x00, 0xc0, 0x00, 0x3c, 0xff, 0x7b, 0x3f, 0x80]
+    );
+}
+
+#[test]
+fn x87_conversion() {
+    assert_eq!(f64_to_x87(1.0), (0x3fff, 0x8000_0000_0000_0000));
+    assert_eq!(f64_to_x87(-44100.0), (0xc00e, 0xac44_0000_0000_0000));
+    assert_eq!(
+        f64_to_x87(f64::from_bits(1)),
+        (0x3bcd, 0x8000_0000_0000_0000)
+    );
+    assert_eq!(f64_to_x87(f64::INFINITY), (0x7fff, 0x8000_0000_0000_0000));
+
+    assert_eq!(x87_to_f64(0x3fff, u64::MAX), 2.0);
+    assert_eq!(x87_to_f64(0x4000, 0xc000_0000_0000_0400), 3.0);
+    assert_eq!(
+        x87_to_f64(0x4000, 0xc000_0000_0000_0401),
+        3.0 + 2f64.powi(-51)
+    );
+    assert_eq!(x87_to_f64(0x7ffe, 1 << 63), f64::INFINITY);
+    assert_eq!(x87_to_f64(0x0001, 1 << 63), 0.0);
+    assert_eq!(x87_to_f64(0x3bcd, 3 << 62), f64::from_bits(2));
+    assert!(x87_to_f64(0xffff, 0xc000_0000_0000_0000).is_nan());
+
+    for value in [0.1, -1e300, 5e-324, f64::MAX, f64::MIN_POSITIVE] {
+        let (sign_exp, mantissa) = f64_to_x87(value);
+        assert_eq!(x87_to_f64(sign_exp, mantissa), value);
+    }
+}
+
+#[test]
+fn ibm_conversion() {
+    assert_eq!(ibm32_to_f64(0xc276_a000), -118.625);
+    assert_eq!(ibm32_to_f64(0x4110_0000), 1.0);
+    assert_eq!(ibm32_to_f64(0x4100_0001), 2f64.powi(-20));
+    assert_eq!(ibm64_to_f64(0x4110_0000_0000_0000), 1.0);
+    assert_eq!(ibm64_to_f64(0x41ff_ffff_ffff_ffff), 16.0);
+
+    assert_eq!(f64_to_ibm32(-118.625).unwrap(), 0xc276_a000);
+    assert_eq!(f64_to_ibm32(0.1).unwrap(), 0x4019_999a);
+    assert_eq!(f64_to_ibm32(1.0 - 2f64.powi(-30)).unwrap(), 0x4110_0000);
+    assert_eq!(f64_to_ibm32(1e-80).unwrap(), 0);
+    assert_eq!(f64_to_ibm64(0.1).unwrap(), 0x4019_9999_9999_999a);
+
+    assert!(matches!(
+        f64_to_ibm32(1e76),
+        Err(Error::Unrepresentable {
+            type_name: "ibm32",
+            ..
+        })
+    ));
+    assert!(f64_to_ibm64(f64::NAN).is_err());
+
+    for value in [0.1, -3.75, 7e75, 6e-79, 123456.789] {
+        assert_eq!(ibm64_to_f64(f64_to_ibm64(value).unwrap()), value);
+    }
+}
+
+#[test]
+fn vax_conversion() {
+    assert_eq!(f64_to_vax_f(1.0).unwrap(), [0x80, 0x40, 0x00, 0x00]);
+    assert_eq!(f64_to_vax_f(-0.75).unwrap(), [0x40, 0xc0, 0x00, 0x00]);
+    assert_eq!(f64_to_vax_d(1.0).unwrap(), [0x80, 0x40, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(f64_to_vax_g(1.0).unwrap(), [0x10, 0x40, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(f64_to_vax_f(-0.0).unwrap(), [0; 4]);
+    assert_eq!(f64_to_vax_f(1e-40).unwrap(), [0; 4]);
+    assert!(f64_to_vax_f(1e39).is_err());
+    assert!(f64_to_vax_g(f64::MAX).is_err());
+    assert!(f64_to_vax_d(f64::INFINITY).is_err());
+
+    assert_eq!(vax_f_to_f64([0x80, 0x40, 0x00, 0x00]).unwrap(), 1.0);
+    assert_eq!(vax_f_to_f64([0x00, 0x00, 0x34, 0x12]).unwrap(), 0.0);
+    assert!(matches!(
+        vax_f_to_f64([0x00, 0x80, 0x00, 0x00]),
+        Err(Error::InvalidEncoding {
+            type_name: "vax_f",
+            ..
+        })
+    ));
+    assert_eq!(vax_d_to_f64([0x80, 0x40, 0, 0, 0, 0, 0, 0]).unwrap(), 1.0);
+
+    for value in [0.1, -3.75, 1.7e38, 3e-39] {
+        assert_eq!(vax_d_to_f64(f64_to_vax_d(value).unwrap()).unwrap(), value);
+    }
+    for value in [0.1, -3.75, 8.9e307, 5.6e-309] {
+        assert_eq!(vax_g_to_f64(f64_to_vax_g(value).unwrap()).unwrap(), value);
+    }
+    assert_eq!(
+        vax_f_to_f64(f64_to_vax_f(0.1).unwrap()).unwrap(),
+        0.1f32 as f64
+    );
+}
+
+#[test]
+fn read_write_legacy() {
+    let mut cursor = Cursor::new(Vec::new());
+
+    cursor.write_f80_be(44100.0).unwrap();
+    cursor.write_f80_le(-1.0).unwrap();
+    cursor.write_ibm32_be(-118.625).unwrap();
+    cursor.write_vax_f(1.0).unwrap();
+    cursor.write_ibm64(Little, 0.5).unwrap();
+    assert!(matches!(
+        cursor.write_ibm32_be_at(2, f64::INFINITY),
+        Err(Error::Unrepresentable {
+            offset: Some(2),
+            ..
+        })
+    ));
+
+    assert_eq!(
+        cursor.get_ref()[..28],
+        [
+            0x40, 0x0e, 0xac, 0x44, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0xff, 0xbf, 0xc2,
+            0x76, 0xa0, 0x00, 0x80, 0x40, 0x00, 0x00
+        ]
+    );
+
+    cursor.set_position(0);
+    assert_eq!(cursor.read_f80_be().unwrap(), 44100.0);
+    assert_eq!(cursor.read_f80(Little).unwrap(), -1.0);
+    assert_eq!(cursor.read_ibm32_be().unwrap(), -118.625);
+    assert_eq!(cursor.read_vax_f().unwrap(), 1.0);
+    assert_eq!(cursor.read_ibm64_le().unwrap(), 0.5);
+    assert_eq!(cursor.read_ibm32_be_at(20).unwrap(), -118.625);
+
+    cursor.write_u8_array_at(24, [0x00, 0x80]).unwrap();
+    assert!(matches!(
+        cursor.read_vax_f_at(24),
+        Err(Error::InvalidEncoding {
+            offset: Some(24),
+            ..
+        })
+    ));
+}
@@ -0,0 +1,56 @@
+use dh::{
+    ChecksumReader, ReadVal, ReadValAt,
+    hash::{Md5, Sha1, Sha256, digest},
+};
+use std::io::Cursor;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[test]
+fn vectors() {
+    assert_eq!(hex(&digest::<Md5>(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(
+        hex(&digest::<Md5>(b"abc")),
+        "900150983cd24fb0d6963f7d28e17f72"
+    );
+    assert_eq!(
+        hex(&digest::<Sha1>(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+    assert_eq!(
+        hex(&digest::<Sha256>(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex(&digest::<Sha256>(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        hex(&digest::<Sha1>(&[b'a'; 1_000_000])),
+        "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+    );
+}
+
+#[test]
+fn reader() {
+    let mut data = vec![7; 1000];
+    data.extend(digest::<Sha256>(&[7; 1000]));
+
+    let mut reader = ChecksumReader::new(Cursor::new(&data), Sha256::new());
+    reader.copy(1000, &mut std::io::sink()).unwrap();
+    let hash = reader.value();
+    assert_eq!(reader.read_u8_array::<32>().unwrap(), hash);
+}
+
+#[test]
+fn range() {
+    let mut data = Cursor::new(b"..abc..".to_vec());
+    let hash = data.hash_at::<Md5>(2, 3).unwrap();
+    assert_eq!(hex(&hash), "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(data.position(), 0);
+    assert!(data.hash_at::<Sha1>(5, 3).is_err());
+}
@@ -0,0 +1,118 @@
+use dh::{Error, Limited, ReadVal, ReadValAt};
+use std::io::{Cursor, ErrorKind, Read, Seek, SeekFrom};
+
+#[test]
+fn limit_total() {
+    let data = [1u8; 8];
+    let mut reader = Limited::new(&data[..], 6);
+
+    assert_eq!(reader.read_u32_le().unwrap(), 0x01010101);
+    assert_eq!(reader.remaining(), 2);
+
+    let err = reader.read_u32_le().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+    assert_eq!(reader.consumed(), 6);
+}
+
+#[test]
+fn limit_dynamic() {
+    let data = [1u8; 8];
+    let mut reader = Limited::new(&data[..], 1 << 30).with_call_limit(4);
+
+    assert_eq!(reader.read_vec(4).unwrap(), [1; 4]);
+
+    let err = reader.read_vec(usize::MAX).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::LimitExceeded {
+            requested,
+            limit: 4,
+            ..
+        } if requested == usize::MAX as u64
+    ));
+    assert_eq!(reader.consumed(), 4);
+
+    let mut reader = Limited::new(&data[..], 2);
+    assert!(matches!(
+        reader.read_str(3).unwrap_err(),
+        Error::LimitExceeded { limit: 2, .. }
+    ));
+}
+
+#[test]
+fn limit_seek() {
+    let mut reader = Limited::new(Cursor::new([0u8, 1, 2, 3]), 3);
+
+    assert_eq!(reader.read_u16_be_at(2).unwrap(), 0x0203);
+    assert_eq!(reader.read_u8().unwrap(), 0);
+    assert!(reader.read_u8().is_err());
+}
+
+#[test]
+fn limit_truncated() {
+    let data = [1u8; 8];
+    let mut cursor = Cursor::new(data);
+
+    // fails without allocating the requested length
+    let err = cursor.read_vec(usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    let err = cursor.read_vec_at(4, usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+fn read_name<R: ReadVal>(reader: &mut R) -> dh::Result<String> {
+    let len = reader.read_u8()?;
+    reader.read_str(len as usize)
+}
+
+#[test]
+fn limit_trait_path() {
+    let data = [200u8, b'a', b'b', b'c', 2, b'd', b'e'];
+
+    // generic code is bound by the total limit
+    let mut reader = Limited::new(Cursor::new(data), 4);
+    let err = read_name(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+
+    // the methods of the wrapper check the call limit before reading
+    let mut reader = Limited::new(Cursor::new(data), 1 << 30).with_call_limit(4);
+    let len = reader.read_u8().unwrap();
+    assert!(matches!(
+        reader.read_str(len as usize).unwrap_err(),
+        Error::LimitExceeded {
+            requested: 200,
+            limit: 4,
+            ..
+        }
+    ));
+    assert_eq!(reader.consumed(), 1);
+    reader.seek(SeekFrom::Start(4)).unwrap();
+    assert_eq!(read_name(&mut reader).unwrap(), "de");
+}
+
+#[test]
+fn limit_inner_after_limit() {
+    let data = [1u8, 2, 3, 4, 5];
+    let mut reader = Limited::new(&data[..], 2);
+    assert!(reader.read_u32_le().is_err());
+    let mut rest = Vec::new();
+    reader.into_inner().read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [3, 4, 5]);
+
+    let mut reader = Limited::new(Cursor::new(data), 2);
+    assert!(reader.read_u32_le().is_err());
+    assert_eq!(reader.stream_position().unwrap(), 2);
+    assert_eq!(reader.seek(SeekFrom::Current(1)).unwrap(), 3);
+}
+
+#[test]
+fn limit_eof() {
+    let data = [1u8, 2, 3];
+    let mut buf = Vec::new();
+    Limited::new(&data[..], 3).read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, data);
+
+    let mut reader = Limited::new(&data[..], 2);
+    let err = reader.read_to_end(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::QuotaExceeded);
+}
@@ -0,0 +1,105 @@
+use dh::{Endianess::*, Error, ReadVal, ReadValAt, WriteVal, WriteValAt};
+use std::io::Cursor;
+
+#[test]
+fn read_odd() {
+    let data = [
+        0x01u8, 0x02, 0x03, 0xff, 0xff, 0xfe, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55,
+    ];
+    let mut cursor = Cursor::new(data);
+
+    assert_eq!(cursor.read_u24_le().unwrap(), 0x030201);
+    assert_eq!(cursor.read_i24_be().unwrap(), -2);
+    assert_eq!(cursor.read_u48(Big).unwrap(), 0x001122334455);
+
+    cursor.set_position(0);
+    assert_eq!(cursor.read_uint_n(5, Big).unwrap(), 0x010203ffff);
+    assert_eq!(cursor.read_int_n(1, Little).unwrap(), -2);
+    assert!(cursor.read_uint_n(9, Little).is_err());
+    assert!(cursor.read_int_n(9, Little).is_err());
+    assert!(matches!(
+        cursor.read_int_n(0, Big).unwrap_err(),
+        Error::OutOfRange {
+            value: 0,
+            type_name: "integer width",
+            ..
+        }
+    ));
+
+    let expected = match cfg!(target_endian = "big") {
+        true => 0x001122,
+        false => 0x221100,
+    };
+    assert_eq!(cursor.read_u24_ne().unwrap(), expected);
+
+    // overflow
+    assert!(matches!(
+        cursor.read_u40_le().unwrap_err(),
+        Error::UnexpectedEof {
+            type_name: "u40",
+            size: 5,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn read_odd_at() {
+    let data = [0u8, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+    let mut cursor = Cursor::new(data);
+
+    assert_eq!(cursor.read_i56_le_at(1).unwrap(), 0x0001_0000_0000_0080);
+    assert_eq!(cursor.read_i40_be_at(1).unwrap(), -0x80_0000_0000);
+    assert_eq!(cursor.read_u24_at(5, Big).unwrap(), 1);
+    assert_eq!(cursor.position(), 0);
+
+    let err = cursor.read_u48_be_at(4).unwrap_err();
+    assert_eq!(err.offset(), Some(4));
+}
+
+#[test]
+fn write_odd() {
+    let mut cursor = Cursor::new(Vec::new());
+
+    cursor.write_u24_le(0x030201).unwrap();
+    cursor.write_i24_be(-2).unwrap();
+    cursor.write_u40(Big, 0x0102030405).unwrap();
+    cursor.write_int_n(2, Little, -1).unwrap();
+
+    assert_eq!(
+        cursor.get_ref(),
+        &[1, 2, 3, 0xff, 0xff, 0xfe, 1, 2, 3, 4, 5, 0xff, 0xff]
+    );
+
+    // out of range
+    assert!(matches!(
+        cursor.write_u24_le(0x01000000).unwrap_err(),
+        Error::OutOfRange {
+            value: 0x01000000,
+            type_name: "u24",
+            ..
+        }
+    ));
+    assert!(cursor.write_i24_le(0x800000).is_err());
+    assert!(cursor.write_i24_le(-0x800001).is_err());
+    assert!(cursor.write_i24_le(-0x800000).is_ok());
+    assert!(cursor.write_uint_n(0, Little, 0).is_err());
+}
+
+#[test]
+fn write_odd_at() {
+    let mut cursor = Cursor::new([0u8; 8]);
+
+    cursor.write_u56_be_at(1, 0x01020304050607).unwrap();
+    cursor.write_i48_le_at(0, -1).unwrap();
+    cursor.write_u24_at(5, Little, 0xaabbcc).unwrap();
+
+    assert_eq!(
+        cursor.get_ref(),
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xcc, 0xbb, 0xaa]
+    );
+    assert_eq!(cursor.position(), 0);
+
+    let err = cursor.write_u48_le_at(1, 1 << 48).unwrap_err();
+    assert_eq!(err.offset(), Some(1));
+}
@@ -0,0 +1,122 @@
+use dh::encoding::Encoding;
+use dh::patch::{self, Patch, Record};
+use std::io::Cursor;
+
+fn versions() -> (Cursor<Vec<u8>>, Cursor<Vec<u8>>) {
+    let old: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    let mut new = old.clone();
+    new[10..13].copy_from_slice(b"abc");
+    new[65535..65540].fill(0);
+    new.extend([0x42; 20]);
+    (Cursor::new(old), Cursor::new(new))
+}
+
+#[test]
+fn diff() {
+    let (mut old, mut new) = versions();
+    new.set_position(7);
+    let ranges = patch::diff(&mut old, &mut new).unwrap();
+    assert_eq!(ranges, [10..13, 65535..65540, 100_000..100_020]);
+    assert_eq!(new.position(), 7);
+    assert_eq!(
+        patch::diff(&mut new, &mut old).unwrap(),
+        [10..13, 65535..65540]
+    );
+}
+
+#[test]
+fn compact() {
+    let (mut old, mut new) = versions();
+    let patch = Patch::diff(&mut old, &mut new).unwrap();
+    assert_eq!(patch.truncate, None);
+    assert_eq!(patch.records[0].data, b"abc");
+
+    let mut written = Vec::new();
+    patch.write(&mut written).unwrap();
+    assert!(written.len() < 50);
+    let read = Patch::read(&mut Cursor::new(written)).unwrap();
+    assert_eq!(read, patch);
+
+    let mut target = Cursor::new(Vec::new());
+    read.apply(&mut old, &mut target).unwrap();
+    assert_eq!(target.get_ref(), new.get_ref());
+
+    // the other way around truncates
+    let patch = Patch::diff(&mut new, &mut old).unwrap();
+    assert_eq!(patch.truncate, Some(100_000));
+    let mut target = Cursor::new(Vec::new());
+    patch.apply(&mut new, &mut target).unwrap();
+    assert_eq!(target.get_ref(), old.get_ref());
+}
+
+#[test]
+fn ips() {
+    let patch = Patch {
+        truncate: Some(0x30),
+        records: vec![Record {
+            offset: 0x10,
+            data: b"ab\0\0\0\0\0\0\0\0\0\0c".to_vec(),
+        }],
+    };
+    let mut written = Vec::new();
+    patch.write_ips(&mut written).unwrap();
+    let expected = Encoding::HEX_LOWER
+        .decode("5041544348 000010 0002 6162 000012 0000 000a 00 00001c 0001 63 454f46 000030")
+        .unwrap();
+    assert_eq!(written, expected);
+    let read = Patch::read_ips(&mut Cursor::new(written)).unwrap();
+    assert_eq!(read.truncate, Some(0x30));
+    assert_eq!(read.records.len(), 3);
+    assert_eq!(
+        read.records[1],
+        Record {
+            offset: 0x12,
+            data: vec![0; 10],
+        }
+    );
+
+    let (mut old, mut new) = versions();
+    let patch = Patch::diff(&mut old, &mut new).unwrap();
+    let mut written = Vec::new();
+    patch.write_ips(&mut written).unwrap();
+    let mut target = Cursor::new(Vec::new());
+    Patch::read_ips(&mut Cursor::new(written))
+        .unwrap()
+        .apply(&mut old, &mut target)
+        .unwrap();
+    assert_eq!(target.get_ref(), new.get_ref());
+
+    let eof = Patch {
+        truncate: None,
+        records: vec![Record {
+            offset: 0x454f46,
+            data: vec![1],
+        }],
+    };
+    assert!(eof.write_ips(&mut Vec::new()).is_err());
+}
+
+#[test]
+fn bps() {
+    let (mut old, mut new) = versions();
+    let mut written = Vec::new();
+    patch::write_bps(&mut old, &mut new, &mut written).unwrap();
+    assert!(written.len() < 70);
+    let mut target = Vec::new();
+    patch::apply_bps(&mut old, &mut Cursor::new(&written), &mut target).unwrap();
+    assert_eq!(&target, new.get_ref());
+
+    // wrong source
+    assert!(patch::apply_bps(&mut new, &mut Cursor::new(&written), &mut Vec::new()).is_err());
+    let last = written.len() - 1;
+    written[last] ^= 1;
+    assert!(patch::apply_bps(&mut old, &mut Cursor::new(&written), &mut Vec::new()).is_err());
+
+    // source copy and overlapping target copy
+    let written = Encoding::HEX_LOWER
+        .decode("42505331868980848a8681788b8aef398e4b16e93c43b08489a8")
+        .unwrap();
+    let mut target = Vec::new();
+    patch::apply_bps(&mut Cursor::new(b"abcdef"), &mut &written[..], &mut target).unwrap();
+    assert_eq!(target, b"abdefxxxx");
+}
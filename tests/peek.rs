@@ -0,0 +1,92 @@
+use dh::{CountingReader, Endianess::Big, PeekVal, Pushback, ReadVal};
+use std::io::{Cursor, ErrorKind, Read};
+
+#[test]
+fn peek_seekable() {
+    let data = [0x89u8, b'P', b'N', b'G', 1, 2];
+    let mut cursor = Cursor::new(data);
+
+    assert_eq!(cursor.peek_u32_be().unwrap(), 0x89504e47);
+    assert_eq!(
+        cursor.peek_u8_array::<4>().unwrap(),
+        [0x89, b'P', b'N', b'G']
+    );
+    assert_eq!(cursor.read_u8().unwrap(), 0x89);
+    assert_eq!(cursor.peek_str(3).unwrap(), "PNG");
+    assert_eq!(cursor.position(), 1);
+
+    // overflow
+    assert!(cursor.peek_vec(6).is_err());
+    assert_eq!(cursor.position(), 1);
+}
+
+#[test]
+fn peek_pushback() {
+    let data = [0u8, 1, 2, 3];
+    let mut reader = Pushback::new(&data[..]);
+
+    assert_eq!(reader.peek_u16(Big).unwrap(), 0x0001);
+    assert_eq!(reader.peek_vec(3).unwrap(), [0, 1, 2]);
+    assert_eq!(reader.read_u16_le().unwrap(), 0x0100);
+    assert_eq!(reader.peek_u8().unwrap(), 2);
+    assert_eq!(reader.read_vec(2).unwrap(), [2, 3]);
+
+    // overflow
+    assert!(reader.peek_u8().is_err());
+}
+
+#[test]
+fn peek_unread() {
+    let data = [2u8, 3];
+    let mut reader = Pushback::new(&data[..]);
+
+    let first = reader.read_u8().unwrap();
+    reader.unread(&[0, 1, first]);
+    assert_eq!(reader.buffer().len(), 3);
+    assert_eq!(reader.read_vec(4).unwrap(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn peek_counting() {
+    let data = [0u8, 1, 2, 3];
+    let mut reader = CountingReader::new(&data[..]);
+
+    assert_eq!(reader.peek_u16_le().unwrap(), 0x0100);
+    assert_eq!(reader.read_u8().unwrap(), 0);
+    assert_eq!(reader.position(), 1);
+}
+
+struct Interrupting<'a> {
+    data: &'a [u8],
+    interrupt: bool,
+}
+
+impl Read for Interrupting<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        match self.interrupt {
+            true => Err(ErrorKind::Interrupted.into()),
+            false => self.data.read(buf),
+        }
+    }
+}
+
+#[test]
+fn peek_large_len() {
+    // fails at the end of the stream instead of allocating the whole length up front
+    let mut cursor = Cursor::new([1u8, 2, 3]);
+    assert!(cursor.peek_vec(1 << 40).is_err());
+    assert_eq!(cursor.position(), 0);
+    let mut reader = Pushback::new(&[1u8, 2, 3][..]);
+    assert!(reader.peek_vec(1 << 40).is_err());
+
+    let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+    assert_eq!(Cursor::new(&data).peek_vec(20000).unwrap(), data);
+
+    let mut reader = Pushback::new(Interrupting {
+        data: &data,
+        interrupt: false,
+    });
+    assert_eq!(reader.peek_vec(20000).unwrap(), data);
+    assert_eq!(reader.read_vec(20000).unwrap(), data);
+}
@@ -0,0 +1,92 @@
+use dh::{Be, Error, Le, Pod, ReadVal, U16, U32, U128, WriteVal, pod};
+use std::io::Cursor;
+
+pod! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Record {
+        id: U32<Le>,
+        kind: U16<Be>,
+        flags: [u8; 2],
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, align(4))]
+struct Aligned([u8; 4]);
+
+unsafe impl Pod for Aligned {}
+
+#[test]
+fn pod_read() {
+    let data = [1u8, 0, 0, 0, 0, 2, 3, 4, 5, 0, 0, 0, 0, 6, 7, 8];
+    let mut cursor = Cursor::new(data);
+
+    let record: Record = cursor.read_pod().unwrap();
+    assert_eq!(record.id.get(), 1);
+    assert_eq!(record.kind.get(), 2);
+    assert_eq!(record.flags, [3, 4]);
+
+    cursor.set_position(0);
+    let records: Vec<Record> = cursor.read_pod_slice(2).unwrap();
+    assert_eq!(records[0], record);
+    assert_eq!(records[1].id.get(), 5);
+
+    // overflow
+    assert!(cursor.read_pod::<Record>().is_err());
+    cursor.set_position(0);
+    assert!(cursor.read_pod_slice::<Record>(usize::MAX / 8).is_err());
+    assert!(matches!(
+        cursor.read_pod_slice::<U128<Le>>(usize::MAX).unwrap_err(),
+        Error::OutOfRange { .. }
+    ));
+    // zero-sized types are rejected instead of looping over the length
+    assert!(matches!(
+        cursor.read_pod_slice::<()>(usize::MAX).unwrap_err(),
+        Error::SizeMismatch { .. }
+    ));
+    assert!(cursor.read_pod_slice::<[u8; 0]>(1).is_err());
+}
+
+#[test]
+fn pod_write() {
+    let record = Record {
+        id: U32::new(1),
+        kind: U16::new(2),
+        flags: [3, 4],
+    };
+    let mut cursor = Cursor::new(Vec::new());
+
+    cursor.write_pod(&record).unwrap();
+    cursor.write_pod_slice(&[U16::<Le>::new(5)]).unwrap();
+
+    assert_eq!(cursor.into_inner(), [1, 0, 0, 0, 0, 2, 3, 4, 5, 0]);
+}
+
+#[test]
+fn pod_borrow() {
+    let data = [1u8, 0, 0, 0, 0, 2, 3, 4, 5, 0, 0, 0, 0, 6, 7, 8];
+
+    let record: &Record = dh::pod::from_bytes(&data[..8]).unwrap();
+    assert_eq!(record.kind.get(), 2);
+    let records: &[Record] = dh::pod::slice_from_bytes(&data).unwrap();
+    assert_eq!(records[1].flags, [7, 8]);
+    assert_eq!(dh::pod::bytes_of(&records[1]), &data[8..]);
+
+    assert!(matches!(
+        dh::pod::from_bytes::<Record>(&data),
+        Err(Error::SizeMismatch {
+            expected: 8,
+            actual: 16,
+            ..
+        })
+    ));
+    assert!(dh::pod::slice_from_bytes::<Record>(&data[..12]).is_err());
+
+    let aligned = [Aligned([0; 4]); 2];
+    let bytes = dh::pod::slice_bytes(&aligned);
+    assert!(dh::pod::from_bytes::<Aligned>(&bytes[..4]).is_ok());
+    assert!(matches!(
+        dh::pod::from_bytes::<Aligned>(&bytes[1..5]),
+        Err(Error::Misaligned { align: 4, .. })
+    ));
+}
@@ -0,0 +1,119 @@
+use dh::{BoolMode, Endianess::*, Error, PeekVal, ReadVal, ReadValAt, WriteVal, WriteValAt};
+use std::io::Cursor;
+use std::num::{NonZeroI16, NonZeroU32};
+
+#[test]
+fn char_utf32() {
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_char_le('ä').unwrap();
+    cursor.write_char(Big, '🦀').unwrap();
+    cursor.write_u32_le(0xd800).unwrap();
+    assert_eq!(
+        &cursor.get_ref()[..8],
+        &[0xe4, 0, 0, 0, 0, 0x01, 0xf9, 0x80]
+    );
+
+    cursor.set_position(0);
+    assert_eq!(cursor.read_char_le().unwrap(), 'ä');
+    assert_eq!(cursor.peek_char_be().unwrap(), '🦀');
+    assert_eq!(cursor.read_char(Big).unwrap(), '🦀');
+    assert_eq!(cursor.read_char_le_at(0).unwrap(), 'ä');
+
+    // surrogates are not valid chars
+    assert!(matches!(
+        cursor.read_char_le_at(8),
+        Err(Error::InvalidEncoding {
+            offset: Some(8),
+            type_name: "char",
+            ..
+        })
+    ));
+    assert!(cursor.read_char_le_at(4).is_err());
+}
+
+#[test]
+fn char_utf8() {
+    let mut cursor = Cursor::new(Vec::new());
+    for char in ['a', 'ä', '€', '🦀'] {
+        cursor.write_char_utf8(char).unwrap();
+    }
+    cursor.write_char_utf8_at(0, 'b').unwrap();
+    assert_eq!(cursor.get_ref(), "bä€🦀".as_bytes());
+
+    cursor.set_position(0);
+    assert_eq!(cursor.read_char_utf8().unwrap(), 'b');
+    assert_eq!(cursor.read_char_utf8().unwrap(), 'ä');
+    assert_eq!(cursor.read_char_utf8().unwrap(), '€');
+    assert_eq!(cursor.read_char_utf8().unwrap(), '🦀');
+    assert_eq!(cursor.read_char_utf8_at(3).unwrap(), '€');
+
+    let mut cursor = Cursor::new([0x80, 0xc3, 0x28, 0xe2, 0x82]);
+    assert!(matches!(
+        cursor.read_char_utf8(),
+        Err(Error::InvalidUtf8 { len: 1, .. })
+    ));
+    assert!(matches!(
+        cursor.read_char_utf8(),
+        Err(Error::InvalidUtf8 { len: 2, .. })
+    ));
+    assert!(matches!(
+        cursor.read_char_utf8(),
+        Err(Error::UnexpectedEof {
+            type_name: "char",
+            size: 3,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn non_zero() {
+    let mut cursor = Cursor::new(vec![0, 0, 0, 0, 0x2a, 0, 0, 0, 0xff, 0xfe]);
+
+    assert!(matches!(
+        cursor.read_le::<NonZeroU32, _, 4>(),
+        Err(Error::InvalidEncoding {
+            reason: "value is zero",
+            ..
+        })
+    ));
+    let value: NonZeroU32 = cursor.read_le().unwrap();
+    assert_eq!(value.get(), 42);
+    let value: NonZeroI16 = cursor.read_be().unwrap();
+    assert_eq!(value.get(), -2);
+    let value: Result<NonZeroU32, _> = cursor.read_ne_at(0);
+    assert_eq!(value.unwrap_err().offset(), Some(0));
+
+    cursor.write_be_at(0, NonZeroU32::new(7).unwrap()).unwrap();
+    assert_eq!(&cursor.get_ref()[..4], &[0, 0, 0, 7]);
+}
+
+#[test]
+fn bool_modes() {
+    let mut cursor = Cursor::new(vec![0x02, 0x01, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00]);
+
+    assert!(!cursor.read_bool_mode(BoolMode::Lsb).unwrap());
+    assert!(cursor.read_bool_mode(BoolMode::Strict).unwrap());
+    assert!(cursor.read_bool16(Big, BoolMode::Strict).unwrap());
+    assert!(cursor.read_bool32(Little, BoolMode::Strict).unwrap());
+
+    assert!(cursor.read_bool_mode_at(0, BoolMode::NonZero).unwrap());
+    assert!(cursor.read_bool16_at(0, Little, BoolMode::NonZero).unwrap());
+    assert!(matches!(
+        cursor.read_bool16_at(0, Little, BoolMode::Strict),
+        Err(Error::InvalidEncoding {
+            offset: Some(0),
+            type_name: "bool",
+            ..
+        })
+    ));
+    assert!(cursor.read_bool_mode_at(0, BoolMode::Strict).is_err());
+    assert!(cursor.read_bool32_at(4, Big, BoolMode::Strict).is_err());
+
+    let mut cursor = Cursor::new(Vec::new());
+    cursor.write_bool16(Big, true).unwrap();
+    cursor.write_bool32(Little, true).unwrap();
+    cursor.write_bool32_at(2, Big, false).unwrap();
+    cursor.write_bool16_at(0, Little, false).unwrap();
+    assert_eq!(cursor.into_inner(), [0, 0, 0, 0, 0, 0]);
+}
@@ -0,0 +1,65 @@
+use dh::{Pattern, ReadValAt};
+use std::io::Cursor;
+
+fn data() -> Cursor<Vec<u8>> {
+    let mut data = vec![0; 200_000];
+    for pos in [10, 65534, 131_071, 199_994] {
+        data[pos..pos + 6].copy_from_slice(&[0x4d, 0x5a, pos as u8, 0, 0x50, 0x45]);
+    }
+    Cursor::new(data)
+}
+
+#[test]
+fn patterns() {
+    let pattern = Pattern::parse("4D 5A ?? ?? 50 45").unwrap();
+    assert_eq!(pattern.len(), 6);
+    assert!(pattern.matches(b"MZ\x90\x00PE\x00"));
+    assert!(!pattern.matches(b"MZ\x90\x00PF"));
+    assert!(!pattern.matches(b"MZ\x90"));
+
+    let nibbles: Pattern = "4? ?a ?".parse().unwrap();
+    assert!(nibbles.matches(&[0x41, 0xfa, 0x00]));
+    assert!(!nibbles.matches(&[0x51, 0xfa, 0x00]));
+    assert_eq!(Pattern::parse("4D5A").unwrap(), Pattern::new(*b"MZ"));
+    assert!(Pattern::parse("4D 5").is_err());
+    assert!(Pattern::parse("XY").is_err());
+}
+
+#[test]
+fn find() {
+    let mut data = data();
+    let pattern = Pattern::parse("4D 5A ?? 00 50 45").unwrap();
+    assert_eq!(data.find(&pattern).unwrap(), Some(10));
+    assert_eq!(
+        data.find_all(&pattern).unwrap(),
+        [10, 65534, 131_071, 199_994]
+    );
+    assert_eq!(data.rfind(&pattern).unwrap(), Some(199_994));
+    assert_eq!(data.find(b"PE\x01").unwrap(), None);
+
+    data.set_position(11);
+    assert_eq!(data.find(&pattern).unwrap(), Some(65534));
+    assert_eq!(data.position(), 11);
+    data.set_position(199_995);
+    assert_eq!(data.rfind(&pattern).unwrap(), None);
+}
+
+#[test]
+fn find_at() {
+    let mut data = data();
+    let pattern = Pattern::parse("4D 5A").unwrap();
+    assert_eq!(data.find_at(11, 65536, &pattern).unwrap(), Some(65534));
+    assert_eq!(data.find_at(11, 65535, &pattern).unwrap(), None);
+    assert_eq!(
+        data.find_at(65535, 200_000, &pattern).unwrap(),
+        Some(131_071)
+    );
+    assert_eq!(
+        data.find_at(199_000, u64::MAX, &pattern).unwrap(),
+        Some(199_994)
+    );
+    assert_eq!(data.find_at(10, 11, &pattern).unwrap(), None);
+
+    let mut overlapping = Cursor::new(b"aaaaa".to_vec());
+    assert_eq!(overlapping.find_all(b"aa").unwrap(), [0, 2]);
+}
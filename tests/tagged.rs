@@ -0,0 +1,53 @@
+use dh::{Be, F64, I16, Le, ReadVal, U32, WriteVal};
+use std::io::Cursor;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Header {
+    magic: U32<Be>,
+    version: I16<Le>,
+}
+
+#[test]
+fn tagged_get_set() {
+    let mut value = U32::<Le>::new(0x01020304);
+    assert_eq!(value.to_bytes(), [4, 3, 2, 1]);
+    assert_eq!(value.get(), 0x01020304);
+
+    value.set(5);
+    assert_eq!(u32::from(value), 5);
+    assert_eq!(U32::<Be>::from(5).to_bytes(), [0, 0, 0, 5]);
+    assert_eq!(format!("{:?}", I16::<Be>::new(-2)), "I16(-2)");
+    assert_eq!(size_of::<F64<Le>>(), 8);
+}
+
+#[test]
+fn tagged_read() {
+    let data = [0x89u8, b'P', b'N', b'G', 0xfe, 0xff];
+    let mut cursor = Cursor::new(data);
+
+    let header = Header {
+        magic: cursor.read_ne().unwrap(),
+        version: cursor.read_ne().unwrap(),
+    };
+    assert_eq!(header.magic.get(), 0x89504e47);
+    assert_eq!(header.version.get(), -2);
+
+    // the byte order of the method is ignored
+    cursor.set_position(0);
+    let magic: U32<Be> = cursor.read_le().unwrap();
+    assert_eq!(magic, header.magic);
+}
+
+#[test]
+fn tagged_write() {
+    let mut cursor = Cursor::new(Vec::new());
+
+    cursor.write_ne(U32::<Be>::new(1)).unwrap();
+    cursor.write_be(I16::<Le>::new(2)).unwrap();
+    cursor.write_ne(F64::<Le>::new(1.5)).unwrap();
+
+    assert_eq!(
+        cursor.into_inner(),
+        [0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0xf8, 0x3f]
+    );
+}
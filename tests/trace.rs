@@ -0,0 +1,115 @@
+use dh::{
+    Endianess::{Big, Little, Native},
+    ReadVal, ReadValAt, TraceEntry, Tracer,
+};
+use std::io::{Cursor, Seek, SeekFrom};
+
+fn data() -> Cursor<Vec<u8>> {
+    Cursor::new(b"RIFF\x24\x00\x00\x00WAVEfmt \x10\x00\x00\x00".to_vec())
+}
+
+#[test]
+fn trace_entries() {
+    let mut tracer = Tracer::new(data());
+    tracer.read_vec(4).unwrap();
+    let size = tracer.traced("size", |r| r.read_u32_le()).unwrap();
+    assert_eq!(size, 0x24);
+    let chunk = tracer
+        .traced("chunk", |r| Ok((r.read_str(4)?, r.read_u32_le_at(16)?)))
+        .unwrap();
+    assert_eq!(chunk.1, 16);
+
+    assert_eq!(
+        tracer.entries()[..2],
+        [
+            TraceEntry {
+                offset: 0,
+                len: 4,
+                type_name: "bytes",
+                value: None,
+                label: None,
+            },
+            TraceEntry {
+                offset: 4,
+                len: 4,
+                type_name: "u32",
+                value: Some("36".to_string()),
+                label: Some("size".to_string()),
+            },
+        ]
+    );
+    assert_eq!(tracer.entries()[2].offset, 8);
+    assert_eq!(tracer.entries()[2].len, 12);
+    assert_eq!(
+        tracer.entries()[2].value.as_deref(),
+        Some(r#"("WAVE", 16)"#)
+    );
+    let gaps = tracer.gaps().unwrap();
+    assert_eq!(gaps.len(), 1);
+    assert_eq!(gaps[0], 12..16);
+    assert!(tracer.traced("eof", |r| r.read_u128_le()).is_err());
+    assert_eq!(tracer.entries().len(), 3);
+}
+
+#[test]
+fn trace_typed_reads() {
+    let mut tracer = Tracer::new(data());
+    tracer.read_vec(4).unwrap();
+    let size: u32 = tracer.read_typed(Little).unwrap();
+    assert_eq!(size, 0x24);
+    tracer.seek(SeekFrom::Start(16)).unwrap();
+    let len: u16 = tracer.read_typed(Big).unwrap();
+    assert_eq!(len, 0x1000);
+    assert!(tracer.read_typed::<u64, _, 8>(Little).is_err());
+
+    // a nested tracer only sees the bytes
+    let mut outer = Tracer::new(Tracer::new(data()));
+    let magic: [u8; 4] = outer.read_typed(Native).unwrap();
+    assert_eq!(&magic, b"RIFF");
+
+    let entries: Vec<_> = tracer
+        .entries()
+        .iter()
+        .chain(outer.entries())
+        .chain(outer.get_ref().entries())
+        .map(|entry| {
+            (
+                entry.offset,
+                entry.len,
+                entry.type_name,
+                entry.value.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (0, 4, "bytes", None),
+            (4, 4, "u32", Some("36")),
+            (16, 2, "u16", Some("4096")),
+            (0, 4, "[u8; 4]", Some("[82, 73, 70, 70]")),
+            (0, 4, "bytes", None),
+        ]
+    );
+}
+
+#[test]
+fn trace_json_lines() {
+    let mut tracer = Tracer::new(data());
+    tracer.seek(SeekFrom::Start(8)).unwrap();
+    tracer.traced("form \"type\"", |r| r.read_str(4)).unwrap();
+    tracer.read_u8().unwrap();
+
+    let mut json = Vec::new();
+    tracer.write_json_lines(&mut json).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        concat!(
+            r#"{"offset":8,"len":4,"type":"alloc::string::String","value":"\"WAVE\"","label":"form \"type\""}"#,
+            "\n",
+            r#"{"offset":12,"len":1,"type":"bytes","value":null,"label":null}"#,
+            "\n",
+        )
+    );
+    assert_eq!(tracer.gaps().unwrap(), [0..8, 13..20]);
+}